@@ -5,7 +5,10 @@ use base64::engine::general_purpose::STANDARD as BASE64;
 use base64::Engine;
 use serde::Serialize;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+use super::git::{HighlightedLine, Hunk};
+use super::highlight::{detect_language_from_path, highlight_file_lines};
 
 // ---------------------------------------------------------------------------
 // Editor command template parsing (task 9a)
@@ -188,6 +191,39 @@ pub struct BinaryPreview {
 
 const MAX_PREVIEW_BYTES: usize = 10 * 1024 * 1024;
 
+/// Both sides of an image change as `data:` URLs for `<img src>`
+#[derive(Debug, Serialize)]
+pub struct ImageDiffBase64 {
+    /// None for added files
+    #[serde(rename = "oldDataUrl")]
+    pub old_data_url: Option<String>,
+    /// None for deleted files
+    #[serde(rename = "newDataUrl")]
+    pub new_data_url: Option<String>,
+    #[serde(rename = "mimeType")]
+    pub mime_type: String,
+}
+
+const MAX_IMAGE_DIFF_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Highlighted content of an untracked file, which has no diff to show
+#[derive(Debug, Serialize)]
+pub struct UntrackedPreview {
+    /// None for binary files
+    pub content: Option<String>,
+    pub language: String,
+    #[serde(rename = "sizeBytes")]
+    pub size_bytes: u64,
+    #[serde(rename = "isBinary")]
+    pub is_binary: bool,
+    pub lines: Vec<HighlightedLine>,
+}
+
+const MAX_UNTRACKED_PREVIEW_BYTES: u64 = 5 * 1024 * 1024;
+
+/// How much of a file `is_binary_content` inspects
+const BINARY_SNIFF_BYTES: usize = 8192;
+
 fn detect_mime_type(file_path: &str) -> Option<&'static str> {
     let ext = Path::new(file_path)
         .extension()
@@ -207,7 +243,9 @@ fn detect_mime_type(file_path: &str) -> Option<&'static str> {
     }
 }
 
-fn read_file_from_working_tree(repo_root: &str, file_path: &str) -> Result<Vec<u8>, String> {
+/// Resolve a repo-relative path in the working tree, refusing paths that
+/// escape the repository (via `..` or symlinks)
+fn working_tree_path(repo_root: &str, file_path: &str) -> Result<PathBuf, String> {
     let root = Path::new(repo_root)
         .canonicalize()
         .map_err(|e| format!("Failed to resolve repository root: {}", e))?;
@@ -221,10 +259,30 @@ fn read_file_from_working_tree(repo_root: &str, file_path: &str) -> Result<Vec<u
         return Err("Path escapes repository root".to_string());
     }
 
-    fs::read(canon).map_err(|e| format!("Failed to read file: {}", e))
+    Ok(canon)
+}
+
+fn read_file_from_working_tree(repo_root: &str, file_path: &str) -> Result<Vec<u8>, String> {
+    let path = working_tree_path(repo_root, file_path)?;
+    fs::read(path).map_err(|e| format!("Failed to read file: {}", e))
+}
+
+/// Same heuristic as git: a NUL byte (or invalid UTF-8) near the start means binary
+fn is_binary_content(bytes: &[u8]) -> bool {
+    let sample = &bytes[..bytes.len().min(BINARY_SNIFF_BYTES)];
+    if sample.contains(&0) {
+        return true;
+    }
+    match std::str::from_utf8(sample) {
+        Ok(_) => false,
+        // A multi-byte character cut off by the sample boundary is still text
+        Err(e) => e.error_len().is_some(),
+    }
 }
 
 fn read_file_from_git_ref(repo_root: &str, git_ref: &str, file_path: &str) -> Result<Vec<u8>, String> {
+    // "INDEX" is the staged version: `git show :<path>`
+    let git_ref = if git_ref == "INDEX" { "" } else { git_ref };
     let spec = format!("{}:{}", git_ref, file_path);
     let output = std::process::Command::new("git")
         .args(["show", &spec])
@@ -240,6 +298,27 @@ fn read_file_from_git_ref(repo_root: &str, git_ref: &str, file_path: &str) -> Re
     Ok(output.stdout)
 }
 
+/// Size in bytes of a file at a git ref, without reading its contents
+fn git_object_size(repo_root: &str, git_ref: &str, file_path: &str) -> Result<u64, String> {
+    let git_ref = if git_ref == "INDEX" { "" } else { git_ref };
+    let spec = format!("{}:{}", git_ref, file_path);
+    let output = std::process::Command::new("git")
+        .args(["cat-file", "-s", &spec])
+        .current_dir(repo_root)
+        .output()
+        .map_err(|e| format!("Failed to read file size from git: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("File not available at ref: {}", stderr.trim()));
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .map_err(|e| format!("Failed to parse file size: {}", e))
+}
+
 #[tauri::command]
 pub async fn get_binary_preview(
     repo_root: String,
@@ -275,6 +354,117 @@ pub async fn get_binary_preview(
     })
 }
 
+fn to_data_url(mime_type: &str, bytes: &[u8]) -> String {
+    format!("data:{};base64,{}", mime_type, BASE64.encode(bytes))
+}
+
+/// `old_path` is the base-side path of a renamed image; defaults to `file_path`
+#[tauri::command]
+pub async fn get_image_diff_base64(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    file_path: String,
+    file_status: String,
+    old_path: Option<String>,
+) -> Result<ImageDiffBase64, String> {
+    let mime_type = detect_mime_type(&file_path)
+        .ok_or_else(|| "Preview not supported for this file type".to_string())?;
+    let base_path = old_path.as_deref().unwrap_or(&file_path);
+    let has_old = file_status != "added";
+    let has_new = file_status != "deleted";
+
+    // Check sizes before loading either side into memory
+    let old_size = if has_old {
+        git_object_size(&repo_root, &base_sha, base_path)?
+    } else {
+        0
+    };
+    let new_size = if !has_new {
+        0
+    } else if head_sha == "WORKING_TREE" {
+        fs::metadata(working_tree_path(&repo_root, &file_path)?)
+            .map_err(|e| format!("Failed to read file metadata: {}", e))?
+            .len()
+    } else {
+        git_object_size(&repo_root, &head_sha, &file_path)?
+    };
+    if old_size > MAX_IMAGE_DIFF_BYTES || new_size > MAX_IMAGE_DIFF_BYTES {
+        return Err("Image diff disabled for files larger than 5 MB".to_string());
+    }
+
+    let old_bytes = if has_old {
+        Some(read_file_from_git_ref(&repo_root, &base_sha, base_path)?)
+    } else {
+        None
+    };
+    let new_bytes = if !has_new {
+        None
+    } else if head_sha == "WORKING_TREE" {
+        Some(read_file_from_working_tree(&repo_root, &file_path)?)
+    } else {
+        Some(read_file_from_git_ref(&repo_root, &head_sha, &file_path)?)
+    };
+
+    Ok(ImageDiffBase64 {
+        old_data_url: old_bytes.map(|b| to_data_url(mime_type, &b)),
+        new_data_url: new_bytes.map(|b| to_data_url(mime_type, &b)),
+        mime_type: mime_type.to_string(),
+    })
+}
+
+/// Preview an untracked file from the working tree with syntax highlighting.
+/// Binary files report their size only; files over 5 MB are refused.
+#[tauri::command]
+pub async fn get_untracked_file_preview(
+    repo_root: String,
+    file_path: String,
+) -> Result<UntrackedPreview, String> {
+    let path = working_tree_path(&repo_root, &file_path)?;
+    let size_bytes = fs::metadata(&path)
+        .map_err(|e| format!("Failed to read file metadata: {}", e))?
+        .len();
+    if size_bytes > MAX_UNTRACKED_PREVIEW_BYTES {
+        return Err("Preview disabled for files larger than 5 MB".to_string());
+    }
+
+    let bytes = fs::read(&path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let language = detect_language_from_path(&file_path);
+
+    if is_binary_content(&bytes) {
+        return Ok(UntrackedPreview {
+            content: None,
+            language,
+            size_bytes,
+            is_binary: true,
+            lines: Vec::new(),
+        });
+    }
+
+    let content = String::from_utf8_lossy(&bytes).into_owned();
+    let mut line_highlights = highlight_file_lines(&content, &language);
+    let lines = content
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| HighlightedLine {
+            content: line.to_string(),
+            line_num: idx as u32 + 1,
+            highlights: line_highlights
+                .get_mut(idx)
+                .map(std::mem::take)
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(UntrackedPreview {
+        content: Some(content),
+        language,
+        size_bytes,
+        is_binary: false,
+        lines,
+    })
+}
+
 /// Open a file in the user's editor.
 ///
 /// Resolution order:
@@ -344,6 +534,62 @@ pub async fn open_in_editor(
     Ok(())
 }
 
+/// Reveal a file in the platform file manager (Finder, Explorer, or the
+/// Linux default handler for its parent directory).
+#[tauri::command]
+pub async fn reveal_in_file_manager(app: AppHandle, file_path: String) -> Result<(), String> {
+    let path = Path::new(&file_path);
+    if path
+        .components()
+        .any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        return Err("Path must not contain '..' segments".to_string());
+    }
+
+    let canon = path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve file path: {}", e))?;
+    let target = canon.to_string_lossy().to_string();
+
+    #[cfg(target_os = "macos")]
+    {
+        app.shell()
+            .command("open")
+            .args(["-R", &target])
+            .spawn()
+            .map_err(|e| format!("Failed to reveal file: {}", e))?;
+    }
+    #[cfg(target_os = "windows")]
+    {
+        // canonicalize returns verbatim paths (\\?\C:\...), which Explorer doesn't accept
+        let target = if let Some(share) = target.strip_prefix(r"\\?\UNC\") {
+            format!(r"\\{}", share)
+        } else {
+            target.strip_prefix(r"\\?\").unwrap_or(&target).to_string()
+        };
+        app.shell()
+            .command("explorer.exe")
+            .args([format!("/select,{}", target)])
+            .spawn()
+            .map_err(|e| format!("Failed to reveal file: {}", e))?;
+    }
+    #[cfg(target_os = "linux")]
+    {
+        // xdg-open can't select a file, so open its directory instead
+        let dir = canon
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or(target);
+        app.shell()
+            .command("xdg-open")
+            .args([&dir])
+            .spawn()
+            .map_err(|e| format!("Failed to reveal file: {}", e))?;
+    }
+
+    Ok(())
+}
+
 /// Copy text content to the system clipboard.
 #[tauri::command]
 pub async fn copy_to_clipboard(app: AppHandle, content: String) -> Result<(), String> {
@@ -352,6 +598,104 @@ pub async fn copy_to_clipboard(app: AppHandle, content: String) -> Result<(), St
         .map_err(|e| format!("Failed to copy to clipboard: {}", e))
 }
 
+/// Copy a session file's path to the clipboard, either as given (repo-relative)
+/// or resolved to an absolute path.
+#[tauri::command]
+pub async fn copy_file_path_to_clipboard(
+    app: AppHandle,
+    repo_root: String,
+    file_path: String,
+    absolute: bool,
+) -> Result<(), String> {
+    let content = if absolute {
+        Path::new(&repo_root)
+            .join(&file_path)
+            .canonicalize()
+            .map_err(|e| format!("Failed to resolve file path: {}", e))?
+            .to_string_lossy()
+            .to_string()
+    } else {
+        file_path
+    };
+
+    app.clipboard()
+        .write_text(&content)
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))
+}
+
+/// Copy an absolute path to the clipboard relative to the repo root,
+/// e.g. for pasting into `git show HEAD:<path>`.
+#[tauri::command]
+pub async fn copy_relative_path(
+    app: AppHandle,
+    repo_root: String,
+    absolute_path: String,
+) -> Result<(), String> {
+    let relative = strip_repo_root(&repo_root, &absolute_path)?;
+
+    app.clipboard()
+        .write_text(&relative)
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))
+}
+
+/// Strip `repo_root` from `absolute_path`, returning a `/`-separated relative path.
+fn strip_repo_root(repo_root: &str, absolute_path: &str) -> Result<String, String> {
+    let relative = Path::new(absolute_path)
+        .strip_prefix(repo_root)
+        .map_err(|_| "Path is not inside the repository".to_string())?;
+
+    let parts: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    Ok(parts.join("/"))
+}
+
+/// Format a hunk as a fenced ```diff block under a `path (language)` caption.
+/// The fence grows if the hunk itself contains backtick runs.
+fn format_hunk_markdown(hunk: &Hunk, file_path: &str, language: &str) -> String {
+    let mut body = String::new();
+    body.push_str(&hunk.header);
+    body.push('\n');
+    for line in &hunk.lines {
+        let prefix = match line.line_type.as_str() {
+            "added" => '+',
+            "deleted" => '-',
+            _ => ' ',
+        };
+        body.push(prefix);
+        body.push_str(&line.content);
+        body.push('\n');
+    }
+
+    let mut fence = "```".to_string();
+    while body.contains(&fence) {
+        fence.push('`');
+    }
+
+    let caption = if language.is_empty() || language == "text" {
+        format!("`{}`", file_path)
+    } else {
+        format!("`{}` ({})", file_path, language)
+    };
+    format!("{}\n\n{}diff\n{}{}\n", caption, fence, body, fence)
+}
+
+/// Copy a hunk to the clipboard as a markdown diff block, for pasting into
+/// chat or review comments.
+#[tauri::command]
+pub async fn copy_diff_as_markdown(
+    app: AppHandle,
+    hunk: Hunk,
+    file_path: String,
+    language: String,
+) -> Result<(), String> {
+    let markdown = format_hunk_markdown(&hunk, &file_path, &language);
+    app.clipboard()
+        .write_text(&markdown)
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -391,6 +735,27 @@ mod tests {
         assert_eq!(shell_split("code\t-g\tfile"), vec!["code", "-g", "file"]);
     }
 
+    // -- strip_repo_root -----------------------------------------------------
+
+    #[test]
+    fn strip_repo_root_nested_file() {
+        assert_eq!(
+            strip_repo_root("/repo", "/repo/src/main.rs").unwrap(),
+            "src/main.rs"
+        );
+    }
+
+    #[test]
+    fn strip_repo_root_trailing_slash() {
+        assert_eq!(strip_repo_root("/repo/", "/repo/a.ts").unwrap(), "a.ts");
+    }
+
+    #[test]
+    fn strip_repo_root_outside_repo_errors() {
+        assert!(strip_repo_root("/repo", "/other/a.ts").is_err());
+        assert!(strip_repo_root("/repo", "/repository/a.ts").is_err());
+    }
+
     // -- has_placeholders ----------------------------------------------------
 
     #[test]
@@ -529,4 +894,62 @@ mod tests {
         // Should not duplicate -g
         assert_eq!(args, vec!["-g", "/tmp/a.rs:5"]);
     }
+
+    // -- format_hunk_markdown ------------------------------------------------
+
+    #[test]
+    fn hunk_markdown_prefixes_lines() {
+        use super::super::git::DiffLine;
+
+        let line = |line_type: &str, content: &str| DiffLine {
+            line_type: line_type.to_string(),
+            content: content.to_string(),
+            old_line_num: None,
+            new_line_num: None,
+            highlights: Vec::new(),
+        };
+        let hunk = Hunk {
+            header: "@@ -1,2 +1,2 @@".to_string(),
+            old_start: 1,
+            old_lines: 2,
+            new_start: 1,
+            new_lines: 2,
+            lines: vec![
+                line("context", "fn main() {"),
+                line("deleted", "    old();"),
+                line("added", "    new();"),
+            ],
+            context_name: None,
+            context_language_hint: None,
+        };
+
+        assert_eq!(
+            format_hunk_markdown(&hunk, "src/main.rs", "rust"),
+            "`src/main.rs` (rust)\n\n```diff\n@@ -1,2 +1,2 @@\n fn main() {\n-    old();\n+    new();\n```\n"
+        );
+    }
+
+    // -- to_data_url ---------------------------------------------------------
+
+    #[test]
+    fn data_url_includes_mime_and_base64() {
+        assert_eq!(to_data_url("image/png", b"hi"), "data:image/png;base64,aGk=");
+    }
+
+    // -- is_binary_content ---------------------------------------------------
+
+    #[test]
+    fn binary_content_detection() {
+        assert!(!is_binary_content(b"fn main() {}\n"));
+        assert!(!is_binary_content("caf\u{e9}".as_bytes()));
+        assert!(is_binary_content(b"\x89PNG\r\n\x1a\n\0\0"));
+        assert!(is_binary_content(b"\xff\xfe not utf-8"));
+    }
+
+    #[test]
+    fn binary_content_ignores_char_split_at_sample_end() {
+        let mut bytes = vec![b'a'; BINARY_SNIFF_BYTES - 1];
+        bytes.extend("\u{e9}".as_bytes());
+        assert!(!is_binary_content(&bytes));
+    }
 }
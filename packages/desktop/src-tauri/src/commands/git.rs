@@ -1,21 +1,67 @@
 use lru::LruCache;
 use once_cell::sync::Lazy;
+use rayon::prelude::*;
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use similar::{Algorithm, ChangeTag, TextDiff};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
 use std::num::NonZeroUsize;
-use std::process::Command;
+use std::process::{Command, Stdio};
 use std::sync::Mutex;
 
 use super::highlight::{
-    detect_language_from_path, highlight_file_lines, highlight_line, HighlightSpan,
+    detect_highlight_language, detect_language_from_path, highlight_file_lines, highlight_line,
+    normalize_spans, HighlightSpan,
+};
+use super::session::{
+    find_renames_arg, get_files_for_range, load_review_state, read_rename_threshold, CommentThread,
+    FileEntry,
 };
 
 /// LRU cache for computed diffs
 /// Key: "{repo_root}:{base_sha}:{head_sha}:{file_path}:{ignore_whitespace}"
-/// Capacity: 100 files (typical large PR size)
-static DIFF_CACHE: Lazy<Mutex<LruCache<String, FileDiff>>> =
-    Lazy::new(|| Mutex::new(LruCache::new(NonZeroUsize::new(100).unwrap())));
+/// Capacity: `DEFAULT_DIFF_CACHE_CAPACITY` unless `cache.diffCacheSize` is configured
+static DIFF_CACHE: Lazy<Mutex<LruCache<String, FileDiff>>> = Lazy::new(|| {
+    Mutex::new(LruCache::new(
+        NonZeroUsize::new(DEFAULT_DIFF_CACHE_CAPACITY).unwrap(),
+    ))
+});
+
+/// Diff cache capacity: 100 files (typical large PR size)
+pub const DEFAULT_DIFF_CACHE_CAPACITY: usize = 100;
+
+/// LRU cache for highlighted blame output
+/// Key: "{repo_root}:{commit_sha}:{file_path}" (ref resolved to a SHA, so entries never go stale)
+static BLAME_CACHE: Lazy<Mutex<LruCache<String, Vec<HighlightedBlameLine>>>> =
+    Lazy::new(|| Mutex::new(LruCache::new(NonZeroUsize::new(20).unwrap())));
+
+/// LRU cache for `git diff --shortstat` between two commits
+/// Key: "{repo_root}:{base_sha}:{head_sha}" (commit pairs only, so entries never go stale)
+static SHORTSTAT_CACHE: Lazy<Mutex<LruCache<String, ShortstatSummary>>> =
+    Lazy::new(|| Mutex::new(LruCache::new(NonZeroUsize::new(20).unwrap())));
+
+/// LRU cache for diffs combined with blame of the head
+/// Key: "{repo_root}:{base_sha}:{head_sha}:{file_path}" (commit pairs only)
+/// Separate from DIFF_CACHE since entries combine diff and blame output
+static DIFF_BLAME_CACHE: Lazy<Mutex<LruCache<String, FileDiffWithBlame>>> =
+    Lazy::new(|| Mutex::new(LruCache::new(NonZeroUsize::new(20).unwrap())));
+
+/// LRU cache for per-commit change stats
+/// Key: "{repo_root}:{commit_sha}" (full SHAs only, so entries never go stale)
+static COMMIT_STATS_CACHE: Lazy<Mutex<LruCache<String, CommitWithStats>>> =
+    Lazy::new(|| Mutex::new(LruCache::new(NonZeroUsize::new(500).unwrap())));
+
+/// LRU cache for fully highlighted files at a commit
+/// Key: "{repo_root}:{commit_sha}:{file_path}" (ref resolved to a SHA, so entries never go stale)
+static HIGHLIGHTED_FILE_CACHE: Lazy<Mutex<LruCache<String, HighlightedFile>>> =
+    Lazy::new(|| Mutex::new(LruCache::new(NonZeroUsize::new(20).unwrap())));
+
+/// LRU cache for `diff.<driver>.wordRegex` lookups (`None` when unset)
+/// Key: "{repo_root}:{driver}"; cleared with the diff cache since git config can change
+static WORD_REGEX_CACHE: Lazy<Mutex<LruCache<String, Option<String>>>> =
+    Lazy::new(|| Mutex::new(LruCache::new(NonZeroUsize::new(50).unwrap())));
 
 /// Generate cache key for a diff request
 fn cache_key(
@@ -31,6 +77,41 @@ fn cache_key(
     )
 }
 
+/// Totals for a PR-style header, from `git diff --shortstat`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortstatSummary {
+    #[serde(rename = "filesChanged")]
+    pub files_changed: u32,
+    pub insertions: u32,
+    pub deletions: u32,
+    /// e.g. "5 files changed, 42 insertions(+), 17 deletions(-)"
+    pub formatted: String,
+}
+
+/// Change totals for a single commit in the history view
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitWithStats {
+    pub sha: String,
+    #[serde(rename = "filesChanged")]
+    pub files_changed: u32,
+    pub insertions: u32,
+    pub deletions: u32,
+}
+
+/// A cached diff, for debugging cache behavior
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    #[serde(rename = "filePath")]
+    pub file_path: String,
+    #[serde(rename = "baseSha")]
+    pub base_sha: String,
+    #[serde(rename = "headSha")]
+    pub head_sha: String,
+    /// Estimated from the JSON-serialized diff
+    #[serde(rename = "sizeBytes")]
+    pub size_bytes: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileDiff {
     pub path: String,
@@ -38,6 +119,64 @@ pub struct FileDiff {
     #[serde(rename = "contentHash")]
     pub content_hash: String,
     pub stats: DiffStats,
+    /// True when `.gitattributes` marks the file `-diff`, so no diff was computed
+    #[serde(rename = "skippedByAttributes", default)]
+    pub skipped_by_attributes: bool,
+    /// Human-readable one-liner, e.g. "+42 −17 in 3 hunks"
+    pub summary: String,
+    /// Line ending style of the file's new (or, if deleted, old) content:
+    /// "lf", "crlf", "mixed", or "none"
+    #[serde(rename = "lineEnding", default)]
+    pub line_ending: String,
+    /// Base-side path when diffed across a rename with `get_renamed_file_diff`
+    #[serde(rename = "renamedFrom", default)]
+    pub renamed_from: Option<String>,
+}
+
+/// Line ending breakdown for a file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineEndingInfo {
+    #[serde(rename = "hasCrlf")]
+    pub has_crlf: bool,
+    #[serde(rename = "hasLf")]
+    pub has_lf: bool,
+    #[serde(rename = "isMixed")]
+    pub is_mixed: bool,
+    #[serde(rename = "crlfCount")]
+    pub crlf_count: u32,
+    /// Bare `\n` endings, not counting those in `\r\n`
+    #[serde(rename = "lfCount")]
+    pub lf_count: u32,
+    /// "lf", "crlf", "mixed", or "none" for files without line breaks
+    #[serde(rename = "detectedEnding")]
+    pub detected_ending: String,
+}
+
+/// A file's diff together with the comment threads on it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileDiffWithComments {
+    pub diff: FileDiff,
+    pub threads: Vec<CommentThread>,
+}
+
+/// Uncommitted changes with every file's diff, keyed by path
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UncommittedSnapshot {
+    pub files: Vec<FileEntry>,
+    pub diffs: HashMap<String, FileDiff>,
+}
+
+/// A deleted file's diff with the last commit that touched it before deletion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletedFileDiff {
+    pub diff: FileDiff,
+    #[serde(rename = "deletedAtCommit")]
+    pub deleted_at_commit: String,
+    #[serde(rename = "finalAuthor")]
+    pub final_author: String,
+    /// ISO 8601 author date
+    #[serde(rename = "finalDate")]
+    pub final_date: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,6 +197,12 @@ pub struct Hunk {
     #[serde(rename = "newLines")]
     pub new_lines: u32,
     pub lines: Vec<DiffLine>,
+    /// Enclosing function/class git printed after the closing `@@`
+    #[serde(rename = "contextName", default)]
+    pub context_name: Option<String>,
+    /// The file's detected language, set alongside `context_name`
+    #[serde(rename = "contextLanguageHint", default)]
+    pub context_language_hint: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,6 +217,255 @@ pub struct DiffLine {
     pub highlights: Vec<HighlightSpan>,
 }
 
+/// Word-level change counts for a diff, more telling than line counts for prose
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordStats {
+    /// Words on added lines with no paired deleted line
+    #[serde(rename = "addedWords")]
+    pub added_words: u32,
+    /// Words on deleted lines with no paired added line
+    #[serde(rename = "deletedWords")]
+    pub deleted_words: u32,
+    /// Words inside word-level change ranges of modified lines, counted on both sides
+    #[serde(rename = "changedWords")]
+    pub changed_words: u32,
+}
+
+/// Rough token count for a diff, for checking it against AI model context limits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenCountEstimate {
+    #[serde(rename = "charCount")]
+    pub char_count: u32,
+    #[serde(rename = "estimatedTokens")]
+    pub estimated_tokens: u32,
+    #[serde(rename = "fitsIn4k")]
+    pub fits_in_4k: bool,
+    #[serde(rename = "fitsIn16k")]
+    pub fits_in_16k: bool,
+    #[serde(rename = "fitsIn128k")]
+    pub fits_in_128k: bool,
+}
+
+/// One `<<<<<<<` ... `=======` ... `>>>>>>>` block in a conflicted file.
+/// Line numbers are 1-based and point at the marker lines.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictSection {
+    #[serde(rename = "startLine")]
+    pub start_line: u32,
+    #[serde(rename = "midLine")]
+    pub mid_line: u32,
+    #[serde(rename = "endLine")]
+    pub end_line: u32,
+    #[serde(rename = "oursContent")]
+    pub ours_content: String,
+    #[serde(rename = "theirsContent")]
+    pub theirs_content: String,
+    /// Per-line spans for `ours_content`
+    #[serde(rename = "oursHighlights")]
+    pub ours_highlights: Vec<Vec<HighlightSpan>>,
+    /// Per-line spans for `theirs_content`
+    #[serde(rename = "theirsHighlights")]
+    pub theirs_highlights: Vec<Vec<HighlightSpan>>,
+}
+
+/// Git object mode of a path (e.g. "100644", "100755", "120000", "160000")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileModeInfo {
+    pub mode: String,
+    #[serde(rename = "isExecutable")]
+    pub is_executable: bool,
+    #[serde(rename = "isSymlink")]
+    pub is_symlink: bool,
+    #[serde(rename = "isSubmodule")]
+    pub is_submodule: bool,
+}
+
+/// Maximum number of matches returned by `search_in_session`
+const MAX_SEARCH_RESULTS: usize = 200;
+
+/// A match within a diff line; `match_start`/`match_end` are byte offsets into `content`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResult {
+    #[serde(rename = "filePath")]
+    pub file_path: String,
+    #[serde(rename = "hunkIndex")]
+    pub hunk_index: usize,
+    /// New-side line number, or old-side for deleted lines
+    #[serde(rename = "lineNum")]
+    pub line_num: Option<u32>,
+    pub content: String,
+    #[serde(rename = "matchStart")]
+    pub match_start: usize,
+    #[serde(rename = "matchEnd")]
+    pub match_end: usize,
+}
+
+/// A submodule pointer change, in place of the opaque binary-looking diff
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmoduleDiff {
+    /// Empty when the submodule was added
+    #[serde(rename = "oldSha")]
+    pub old_sha: String,
+    /// Empty when the submodule was removed
+    #[serde(rename = "newSha")]
+    pub new_sha: String,
+    #[serde(rename = "submoduleUrl")]
+    pub submodule_url: Option<String>,
+    /// Commits in the new SHA but not the old; 0 if the submodule isn't initialized
+    #[serde(rename = "commitsAdded")]
+    pub commits_added: u32,
+    /// Commits in the old SHA but not the new; 0 if the submodule isn't initialized
+    #[serde(rename = "commitsRemoved")]
+    pub commits_removed: u32,
+}
+
+/// Position of a line within a `FileDiff`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineLocation {
+    #[serde(rename = "hunkIndex")]
+    pub hunk_index: usize,
+    #[serde(rename = "lineIndexInHunk")]
+    pub line_index_in_hunk: usize,
+}
+
+/// Diff-related `.gitattributes` settings for a file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GitAttributesDiff {
+    /// Custom driver from `diff=<driver>`
+    #[serde(rename = "diffDriver")]
+    pub diff_driver: Option<String>,
+    /// `-diff` (or `binary`): git treats the file as not diffable
+    #[serde(rename = "noDiff")]
+    pub no_diff: bool,
+    pub binary: bool,
+}
+
+/// Oldest git version Revi supports (`--find-renames`, `--diff-algorithm`, ...)
+const MIN_GIT_VERSION: (u32, u32, u32) = (2, 20, 0);
+
+/// Installed git version and whether it meets `MIN_GIT_VERSION`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitVersionInfo {
+    #[serde(rename = "versionString")]
+    pub version_string: String,
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    #[serde(rename = "meetsMinimum")]
+    pub meets_minimum: bool,
+}
+
+/// Maximum number of authors returned by `get_blame_author_stats`
+const MAX_BLAME_AUTHORS: usize = 20;
+
+/// Line ownership for a single author in a file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorStats {
+    pub author: String,
+    pub email: String,
+    #[serde(rename = "lineCount")]
+    pub line_count: u32,
+    /// Fraction of the file's lines (0.0 - 1.0)
+    pub pct: f64,
+}
+
+/// Line counts and size for a file at a ref, for the sidebar's code/blank ratio
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileCodeStats {
+    #[serde(rename = "totalLines")]
+    pub total_lines: u32,
+    #[serde(rename = "blankLines")]
+    pub blank_lines: u32,
+    #[serde(rename = "commentLines")]
+    pub comment_lines: u32,
+    #[serde(rename = "codeLines")]
+    pub code_lines: u32,
+    pub language: String,
+    pub encoding: String,
+    #[serde(rename = "sizeBytes")]
+    pub size_bytes: u64,
+}
+
+/// Size change of a binary file that can't be previewed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinarySizeDiff {
+    /// None when the file was added
+    #[serde(rename = "oldSize")]
+    pub old_size: Option<u64>,
+    /// None when the file was deleted
+    #[serde(rename = "newSize")]
+    pub new_size: Option<u64>,
+    #[serde(rename = "deltaBytes")]
+    pub delta_bytes: i64,
+    /// Change relative to the old size; None when there is no (or an empty) old file
+    #[serde(rename = "deltaPct")]
+    pub delta_pct: Option<f64>,
+}
+
+/// Commit that last touched a line, from `git blame --porcelain`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlameEntry {
+    pub sha: String,
+    pub author: String,
+    #[serde(rename = "authorEmail")]
+    pub author_email: String,
+    /// Unix timestamp of the author date
+    #[serde(rename = "authorTime")]
+    pub author_time: i64,
+    pub summary: String,
+}
+
+/// A file's diff with blame for each head-side line it shows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileDiffWithBlame {
+    pub diff: FileDiff,
+    /// `new_line_num` of added and context lines -> the commit that last touched it
+    pub blame: HashMap<u32, BlameEntry>,
+}
+
+/// A blamed line with syntax highlighting, positions relative to the line start
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HighlightedBlameLine {
+    #[serde(rename = "lineNum")]
+    pub line_num: u32,
+    pub content: String,
+    pub highlights: Vec<HighlightSpan>,
+    pub blame: BlameEntry,
+}
+
+/// A whole file with syntax highlighting, for the file view
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HighlightedFile {
+    pub content: String,
+    pub lines: Vec<HighlightedLine>,
+    pub language: String,
+    pub encoding: String,
+}
+
+/// One line of a `HighlightedFile`, positions relative to the line start
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HighlightedLine {
+    pub content: String,
+    #[serde(rename = "lineNum")]
+    pub line_num: u32,
+    pub highlights: Vec<HighlightSpan>,
+}
+
+/// Maximum number of lines `get_file_context_range` returns
+const MAX_CONTEXT_LINES: u32 = 500;
+
+/// A highlighted line of file content outside any hunk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextLine {
+    #[serde(rename = "lineNum")]
+    pub line_num: u32,
+    pub content: String,
+    pub highlights: Vec<HighlightSpan>,
+}
+
+/// Line prefixes treated as comments by `get_file_stats`
+const COMMENT_PREFIXES: &[&str] = &["//", "#", "/*", "--"];
+
 #[tauri::command]
 pub fn get_file_diff(
     repo_root: String,
@@ -79,9 +473,102 @@ pub fn get_file_diff(
     head_sha: String,
     file_path: String,
     ignore_whitespace: bool,
+    rename_threshold: Option<u8>,
+) -> Result<FileDiff, String> {
+    // Fall back to the threshold the file list was built with
+    let rename_threshold = rename_threshold.or_else(|| read_rename_threshold(&repo_root));
+    compute_file_diff(
+        repo_root,
+        base_sha,
+        head_sha,
+        file_path,
+        None,
+        ignore_whitespace,
+        rename_threshold,
+        true,
+    )
+}
+
+/// Diff a renamed file from `old_path` at the base to `new_path` at the head,
+/// so git pairs the two sides instead of reporting an add and a delete.
+/// Not cached: the cache is keyed by a single path.
+#[tauri::command]
+pub fn get_renamed_file_diff(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    old_path: String,
+    new_path: String,
+    ignore_whitespace: bool,
+) -> Result<FileDiff, String> {
+    compute_file_diff(
+        repo_root,
+        base_sha,
+        head_sha,
+        new_path,
+        Some(old_path),
+        ignore_whitespace,
+        None,
+        false,
+    )
+}
+
+/// Diff a single file from a stash entry against the commit it was made on.
+/// Stash refs shift as entries are pushed and dropped, so these aren't cached.
+#[tauri::command]
+pub fn get_stash_file_diff(
+    repo_root: String,
+    stash_ref: String,
+    file_path: String,
+    ignore_whitespace: bool,
+) -> Result<FileDiff, String> {
+    let stash_sha = resolve_ref(&repo_root, &stash_ref)?;
+    let parent_sha = resolve_ref(&repo_root, &format!("{}^", stash_sha))?;
+    compute_file_diff(
+        repo_root,
+        parent_sha,
+        stash_sha,
+        file_path,
+        None,
+        ignore_whitespace,
+        None,
+        false,
+    )
+}
+
+/// `get_file_diff` with control over the diff cache; `use_cache` is ignored
+/// for diffs that are never cached (working tree, index, custom rename threshold).
+/// `renamed_from` diffs that base-side path against `file_path` on the head side;
+/// with a rename threshold and no `renamed_from`, the source path is looked up.
+#[allow(clippy::too_many_arguments)]
+fn compute_file_diff(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    file_path: String,
+    renamed_from: Option<String>,
+    ignore_whitespace: bool,
+    rename_threshold: Option<u8>,
+    use_cache: bool,
 ) -> Result<FileDiff, String> {
-    // Don't cache working tree diffs (they change frequently)
+    // git can only pair a rename when given both paths
+    let renamed_from = match (renamed_from, rename_threshold) {
+        (None, Some(threshold)) => {
+            find_rename_source(&repo_root, &base_sha, &head_sha, &file_path, threshold)
+        }
+        (renamed_from, _) => renamed_from,
+    };
+    // A path pair only diffs as a rename with rename detection on
+    let find_renames = (rename_threshold.is_some() || renamed_from.is_some())
+        .then(|| find_renames_arg(rename_threshold));
+    let base_path = renamed_from.clone().unwrap_or_else(|| file_path.clone());
+
+    // Don't cache working tree or index diffs (they change frequently)
     let is_working_tree = head_sha == "WORKING_TREE";
+    let is_index_head = head_sha == "INDEX";
+    let is_index_base = base_sha == "INDEX";
+    // Cache keys don't carry the rename threshold, so only default-threshold diffs are cached
+    let cacheable = use_cache && !is_working_tree && !is_index_head && rename_threshold.is_none();
 
     // Check cache first (only for commit-to-commit diffs)
     let key = cache_key(
@@ -91,19 +578,87 @@ pub fn get_file_diff(
         &file_path,
         ignore_whitespace,
     );
-    if !is_working_tree {
+    if cacheable {
         let mut cache = DIFF_CACHE.lock().unwrap_or_else(|e| e.into_inner());
         if let Some(cached) = cache.get(&key) {
             return Ok(cached.clone());
         }
     }
 
+    // Respect `-diff` in .gitattributes: report the file without diffing it.
+    // `binary` also unsets diff, but git still reports those as binary, which
+    // gets the size summary below.
+    let attributes = read_gitattributes_diff(&repo_root, std::slice::from_ref(&file_path))
+        .ok()
+        .and_then(|mut attrs| attrs.remove(&file_path))
+        .unwrap_or_default();
+    if attributes.no_diff && !attributes.binary {
+        return Ok(FileDiff {
+            path: file_path,
+            hunks: Vec::new(),
+            content_hash: String::new(),
+            stats: DiffStats {
+                additions: 0,
+                deletions: 0,
+            },
+            skipped_by_attributes: true,
+            summary: "Diff suppressed by .gitattributes".to_string(),
+            line_ending: String::new(),
+            renamed_from,
+        });
+    }
+
     // Build git diff command based on whether we're comparing to working tree
-    let diff_content = if is_working_tree {
-        // Compare base commit to working tree
-        let mut args = vec!["diff", &base_sha, "--", &file_path];
+    let diff_content = if is_working_tree || is_index_head {
+        // Compare base commit (or the index) to the working tree, or base commit to the index
+        let mut args = vec!["diff"];
         if ignore_whitespace {
-            args.insert(1, "-w");
+            args.push("-w");
+        }
+        if let Some(find_renames) = &find_renames {
+            args.push(find_renames);
+        }
+        if is_index_head {
+            args.push("--cached");
+        }
+        if !is_index_base {
+            args.push(&base_sha);
+        }
+        args.push("--");
+        if renamed_from.is_some() {
+            args.push(&base_path);
+        }
+        args.push(&file_path);
+
+        let output = Command::new("git")
+            .args(&args)
+            .current_dir(&repo_root)
+            .output()
+            .map_err(|e| format!("Failed to execute git diff: {}", e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("git diff failed: {}", stderr));
+        }
+
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    } else {
+        // Compare two commits
+        let mut args = vec![
+            "diff".to_string(),
+            format!("{}...{}", base_sha, head_sha),
+            "--".to_string(),
+        ];
+        if renamed_from.is_some() {
+            args.push(base_path.clone());
+        }
+        args.push(file_path.clone());
+
+        if ignore_whitespace {
+            args.insert(1, "-w".to_string());
+        }
+        if let Some(find_renames) = find_renames {
+            args.insert(1, find_renames);
         }
 
         let output = Command::new("git")
@@ -117,89 +672,1661 @@ pub fn get_file_diff(
             return Err(format!("git diff failed: {}", stderr));
         }
 
-        String::from_utf8_lossy(&output.stdout).into_owned()
-    } else {
-        // Compare two commits
-        let mut args = vec![
-            "diff".to_string(),
-            format!("{}...{}", base_sha, head_sha),
-            "--".to_string(),
-            file_path.clone(),
-        ];
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    // Get file content for syntax highlighting context
+    let head_content = if is_working_tree {
+        // Read current file from working tree
+        get_file_from_working_tree(&repo_root, &file_path).ok()
+    } else {
+        get_file_at_ref(&repo_root, object_ref(&head_sha), &file_path).ok()
+    };
+
+    // Get file content at base for deleted lines
+    let base_content = get_file_at_ref(&repo_root, object_ref(&base_sha), &base_path).ok();
+
+    // Detect language for syntax highlighting, letting content settle ambiguous
+    // extensions and extensionless scripts
+    let language = detect_highlight_language(
+        &file_path,
+        head_content.as_deref().or(base_content.as_deref()),
+    );
+
+    let line_ending = if is_binary_diff(&diff_content) {
+        "none".to_string()
+    } else {
+        head_content
+            .as_deref()
+            .or(base_content.as_deref())
+            .map(|content| analyze_line_endings(content).detected_ending)
+            .unwrap_or_else(|| "none".to_string())
+    };
+
+    // Check if this is a new file (no base content and empty diff but head content exists)
+    let (hunks, stats, content_hash, summary) =
+        if diff_content.trim().is_empty() && base_content.is_none() && head_content.is_some() {
+            // New file: generate synthetic diff showing all lines as additions
+            let file_content = head_content.as_deref().unwrap();
+            let content_hash = compute_hash(file_content);
+            let (hunks, stats, summary) = generate_new_file_diff(file_content, &language);
+            (hunks, stats, content_hash, summary)
+        } else if diff_content.trim().is_empty() && head_content.is_none() && base_content.is_some()
+        {
+            // Deleted file: generate synthetic diff showing all lines as deletions
+            let file_content = base_content.as_deref().unwrap();
+            let content_hash = compute_hash(file_content);
+            let (hunks, stats, summary) = generate_deleted_file_diff(file_content, &language);
+            (hunks, stats, content_hash, summary)
+        } else if is_binary_diff(&diff_content) {
+            // Binary file: git reports no hunks, so summarize the size change instead
+            let content_hash = compute_hash(&diff_content);
+            let old_size = get_size_at(&repo_root, &base_sha, &base_path);
+            let new_size = get_size_at(&repo_root, &head_sha, &file_path);
+            let stats = DiffStats {
+                additions: 0,
+                deletions: 0,
+            };
+            (
+                Vec::new(),
+                stats,
+                content_hash,
+                binary_summary(old_size, new_size),
+            )
+        } else {
+            // Normal diff: parse the git diff output
+            let content_hash = compute_hash(&diff_content);
+            let word_regex = attributes
+                .diff_driver
+                .as_deref()
+                .and_then(|driver| read_driver_word_regex(&repo_root, driver).ok().flatten())
+                .and_then(|pattern| Regex::new(&pattern).ok());
+            let (hunks, stats, summary) = parse_diff_with_highlights(
+                &diff_content,
+                &language,
+                head_content.as_deref(),
+                base_content.as_deref(),
+                word_regex.as_ref(),
+            );
+            (hunks, stats, content_hash, summary)
+        };
+
+    let diff = FileDiff {
+        path: file_path,
+        hunks,
+        content_hash,
+        stats,
+        skipped_by_attributes: false,
+        summary,
+        line_ending,
+        renamed_from,
+    };
+
+    // Store in cache (only for commit-to-commit diffs)
+    if cacheable {
+        let mut cache = DIFF_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        cache.put(key, diff.clone());
+    }
+
+    Ok(diff)
+}
+
+/// Fetch a file's diff and its comment threads from the persisted review state
+/// in one call, so the frontend never renders a diff without its comments
+#[tauri::command]
+pub fn get_file_diff_with_comments(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    file_path: String,
+    ignore_whitespace: bool,
+) -> Result<FileDiffWithComments, String> {
+    let state = load_review_state(repo_root.clone(), base_sha.clone(), head_sha.clone())?;
+    let threads = state
+        .map(|state| {
+            state
+                .threads
+                .into_iter()
+                .filter(|thread| thread.path == file_path)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let diff = get_file_diff(
+        repo_root,
+        base_sha,
+        head_sha,
+        file_path,
+        ignore_whitespace,
+        None,
+    )?;
+
+    Ok(FileDiffWithComments { diff, threads })
+}
+
+/// Diff a single file between two commits without creating a session.
+/// Shares `get_file_diff`'s LRU cache (e.g. for the file history view).
+#[tauri::command]
+pub fn get_file_diff_between_commits(
+    repo_root: String,
+    old_sha: String,
+    new_sha: String,
+    file_path: String,
+    ignore_whitespace: bool,
+) -> Result<FileDiff, String> {
+    get_file_diff(
+        repo_root,
+        old_sha,
+        new_sha,
+        file_path,
+        ignore_whitespace,
+        None,
+    )
+}
+
+/// Get the diff of a file deleted between `base_sha` and `head_sha`, along with
+/// the last commit on the base that modified it
+#[tauri::command]
+pub fn get_diff_for_deleted_file(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    file_path: String,
+) -> Result<DeletedFileDiff, String> {
+    // The index has no history of its own; its last commit is HEAD
+    let base = if base_sha == "INDEX" {
+        "HEAD"
+    } else {
+        base_sha.as_str()
+    };
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%H%n%an%n%aI", base, "--", &file_path])
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to execute git log: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git log failed: {}", stderr.trim()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    let (Some(commit), Some(author), Some(date)) = (lines.next(), lines.next(), lines.next())
+    else {
+        return Err(format!("No history for {} at {}", file_path, base));
+    };
+    let (deleted_at_commit, final_author, final_date) =
+        (commit.to_string(), author.to_string(), date.to_string());
+
+    let diff = get_file_diff(repo_root, base_sha, head_sha, file_path, false, None)?;
+
+    Ok(DeletedFileDiff {
+        diff,
+        deleted_at_commit,
+        final_author,
+        final_date,
+    })
+}
+
+/// List uncommitted changes (HEAD vs working tree) and compute all of their
+/// diffs in one call, in parallel
+#[tauri::command]
+pub fn get_uncommitted_snapshot(repo_root: String) -> Result<UncommittedSnapshot, String> {
+    let head_sha = resolve_ref(&repo_root, "HEAD")?;
+    let files = get_files_for_range(&repo_root, &head_sha, "WORKING_TREE")?;
+
+    let diffs = files
+        .par_iter()
+        .map(|file| {
+            let diff = get_file_diff(
+                repo_root.clone(),
+                head_sha.clone(),
+                "WORKING_TREE".to_string(),
+                file.path.clone(),
+                false,
+                None,
+            )?;
+            Ok((file.path.clone(), diff))
+        })
+        .collect::<Result<HashMap<_, _>, String>>()?;
+
+    Ok(UncommittedSnapshot { files, diffs })
+}
+
+/// Get diff-related `.gitattributes` settings for each of `file_paths`
+#[tauri::command]
+pub fn get_gitattributes_diff_settings(
+    repo_root: String,
+    file_paths: Vec<String>,
+) -> Result<HashMap<String, GitAttributesDiff>, String> {
+    read_gitattributes_diff(&repo_root, &file_paths)
+}
+
+/// Get the `diff.<driver>.wordRegex` configured for the file's diff driver, if any
+#[tauri::command]
+pub fn get_word_regex_for_file(
+    repo_root: String,
+    file_path: String,
+) -> Result<Option<String>, String> {
+    read_word_regex(&repo_root, &file_path)
+}
+
+fn read_word_regex(repo_root: &str, file_path: &str) -> Result<Option<String>, String> {
+    let attributes = read_gitattributes_diff(repo_root, &[file_path.to_string()])?;
+    match attributes
+        .get(file_path)
+        .and_then(|attrs| attrs.diff_driver.as_deref())
+    {
+        Some(driver) => read_driver_word_regex(repo_root, driver),
+        None => Ok(None),
+    }
+}
+
+/// `diff.<driver>.wordRegex` from git config, cached per repo and driver
+fn read_driver_word_regex(repo_root: &str, driver: &str) -> Result<Option<String>, String> {
+    let key = format!("{}:{}", repo_root, driver);
+    {
+        let mut cache = WORD_REGEX_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(cached) = cache.get(&key) {
+            return Ok(cached.clone());
+        }
+    }
+
+    // `git config` exits 1 when the key is unset, which just means no custom regex
+    let output = Command::new("git")
+        .args(["config", "--get", &format!("diff.{}.wordRegex", driver)])
+        .current_dir(repo_root)
+        .output()
+        .map_err(|e| format!("Failed to read git config: {}", e))?;
+
+    let regex = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let regex = (output.status.success() && !regex.is_empty()).then_some(regex);
+
+    let mut cache = WORD_REGEX_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache.put(key, regex.clone());
+
+    Ok(regex)
+}
+
+fn read_gitattributes_diff(
+    repo_root: &str,
+    file_paths: &[String],
+) -> Result<HashMap<String, GitAttributesDiff>, String> {
+    let mut settings: HashMap<String, GitAttributesDiff> = file_paths
+        .iter()
+        .map(|path| (path.clone(), GitAttributesDiff::default()))
+        .collect();
+
+    if file_paths.is_empty() {
+        return Ok(settings);
+    }
+
+    let output = Command::new("git")
+        .args(["check-attr", "diff", "binary", "--"])
+        .args(file_paths)
+        .current_dir(repo_root)
+        .output()
+        .map_err(|e| format!("Failed to execute git check-attr: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git check-attr failed: {}", stderr));
+    }
+
+    // Each line: "<path>: <attribute>: <value>" (the path itself may contain ": ")
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines() {
+        let mut parts = line.rsplitn(3, ": ");
+        let (Some(value), Some(attribute), Some(path)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+
+        let entry = settings.entry(path.to_string()).or_default();
+        match (attribute, value) {
+            ("diff", "unset") => entry.no_diff = true,
+            ("diff", "set" | "unspecified") => {}
+            ("diff", driver) => entry.diff_driver = Some(driver.to_string()),
+            ("binary", "set") => {
+                entry.binary = true;
+                entry.no_diff = true;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(settings)
+}
+
+/// Detect the installed git version and compare it against the minimum
+#[tauri::command]
+pub fn check_git_version() -> Result<GitVersionInfo, String> {
+    let output = Command::new("git")
+        .arg("--version")
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+
+    if !output.status.success() {
+        return Err("git --version failed".to_string());
+    }
+
+    let version_string = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    parse_git_version(&version_string)
+        .ok_or_else(|| format!("Unrecognized git version: {}", version_string))
+}
+
+/// Parse `git version 2.39.3 (Apple Git-145)` / `git version 2.41.0.windows.1`
+fn parse_git_version(version_string: &str) -> Option<GitVersionInfo> {
+    let version = version_string.strip_prefix("git version ")?;
+    let mut numbers = version
+        .split(|c: char| c == '.' || c.is_whitespace())
+        .map(|part| part.parse::<u32>().ok());
+
+    let major = numbers.next()??;
+    let minor = numbers.next()??;
+    let patch = numbers.next().flatten().unwrap_or(0);
+
+    Some(GitVersionInfo {
+        version_string: version_string.to_string(),
+        major,
+        minor,
+        patch,
+        meets_minimum: (major, minor, patch) >= MIN_GIT_VERSION,
+    })
+}
+
+/// Human-readable warning when the installed git is too old, `None` otherwise
+#[tauri::command]
+pub fn get_git_version_warning() -> Result<Option<String>, String> {
+    let info = check_git_version()?;
+    if info.meets_minimum {
+        return Ok(None);
+    }
+    Ok(Some(format!(
+        "Revi requires git {}.{}.{} or newer, but found {}.{}.{}. Some features may not work correctly.",
+        MIN_GIT_VERSION.0, MIN_GIT_VERSION.1, MIN_GIT_VERSION.2, info.major, info.minor, info.patch
+    )))
+}
+
+/// Count lines per author in a file at a ref, most lines first
+#[tauri::command]
+pub fn get_blame_author_stats(
+    repo_root: String,
+    file_path: String,
+    ref_name: String,
+) -> Result<Vec<AuthorStats>, String> {
+    let output = Command::new("git")
+        .args(["blame", "--porcelain", &ref_name, "--", &file_path])
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to execute git blame: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git blame failed: {}", stderr));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_blame_author_stats(&stdout))
+}
+
+/// Get a file's diff along with blame for its added and context lines.
+/// A "WORKING_TREE" head blames the working copy, where uncommitted lines
+/// have an all-zero SHA. Only commit-to-commit results are cached.
+#[tauri::command]
+pub fn get_diff_with_blame(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    file_path: String,
+) -> Result<FileDiffWithBlame, String> {
+    if head_sha == "INDEX" {
+        return Err("Blame is not available for the index".to_string());
+    }
+    let cacheable = head_sha != "WORKING_TREE" && base_sha != "INDEX";
+    let key = format!("{}:{}:{}:{}", repo_root, base_sha, head_sha, file_path);
+    if cacheable {
+        let mut cache = DIFF_BLAME_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(cached) = cache.get(&key) {
+            return Ok(cached.clone());
+        }
+    }
+
+    let diff = get_file_diff(
+        repo_root.clone(),
+        base_sha,
+        head_sha.clone(),
+        file_path.clone(),
+        false,
+        None,
+    )?;
+
+    let wanted: HashSet<u32> = diff
+        .hunks
+        .iter()
+        .flat_map(|hunk| &hunk.lines)
+        .filter(|line| line.line_type != "deleted")
+        .filter_map(|line| line.new_line_num)
+        .collect();
+
+    let mut blame = HashMap::new();
+    if !wanted.is_empty() {
+        let mut args = vec!["blame", "--porcelain"];
+        if head_sha != "WORKING_TREE" {
+            args.push(&head_sha);
+        }
+        args.extend(["--", file_path.as_str()]);
+
+        let output = Command::new("git")
+            .args(&args)
+            .current_dir(&repo_root)
+            .output()
+            .map_err(|e| format!("Failed to execute git blame: {}", e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("git blame failed: {}", stderr));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        blame = parse_blame_porcelain(&stdout)
+            .into_iter()
+            .filter(|(line_num, _, _)| wanted.contains(line_num))
+            .map(|(line_num, _, entry)| (line_num, entry))
+            .collect();
+    }
+
+    let result = FileDiffWithBlame { diff, blame };
+    if cacheable {
+        let mut cache = DIFF_BLAME_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        cache.put(key, result.clone());
+    }
+
+    Ok(result)
+}
+
+/// Blame a file at a ref, with each line syntax-highlighted like diff lines
+#[tauri::command]
+pub fn get_highlighted_blame(
+    repo_root: String,
+    file_path: String,
+    ref_name: String,
+) -> Result<Vec<HighlightedBlameLine>, String> {
+    let commit_sha = resolve_ref(&repo_root, &ref_name)?;
+    let key = format!("{}:{}:{}", repo_root, commit_sha, file_path);
+
+    {
+        let mut cache = BLAME_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(cached) = cache.get(&key) {
+            return Ok(cached.clone());
+        }
+    }
+
+    let output = Command::new("git")
+        .args(["blame", "--porcelain", &commit_sha, "--", &file_path])
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to execute git blame: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git blame failed: {}", stderr));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let blamed = parse_blame_porcelain(&stdout);
+
+    // Highlight the whole file at once so multi-line constructs get the right scopes
+    let content = blamed
+        .iter()
+        .map(|(_, line, _)| *line)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let language = detect_language_from_path(&file_path);
+    let mut line_highlights = highlight_file_lines(&content, &language);
+
+    let lines: Vec<HighlightedBlameLine> = blamed
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (line_num, line, blame))| HighlightedBlameLine {
+            line_num,
+            content: line.to_string(),
+            highlights: line_highlights
+                .get_mut(idx)
+                .map(std::mem::take)
+                .unwrap_or_default(),
+            blame,
+        })
+        .collect();
+
+    {
+        let mut cache = BLAME_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        cache.put(key, lines.clone());
+    }
+
+    Ok(lines)
+}
+
+/// Resolve a ref to its commit SHA
+fn resolve_ref(repo_root: &str, ref_name: &str) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--verify", &format!("{}^{{commit}}", ref_name)])
+        .current_dir(repo_root)
+        .output()
+        .map_err(|e| format!("Failed to resolve ref: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("Unknown ref: {}", ref_name));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Parse `git blame --porcelain` into `(line_num, content, blame)` per line.
+/// Commit headers only appear the first time a commit is seen, so they are
+/// remembered per commit SHA.
+fn parse_blame_porcelain(porcelain: &str) -> Vec<(u32, &str, BlameEntry)> {
+    let mut commits: HashMap<&str, BlameEntry> = HashMap::new();
+    let mut lines = Vec::new();
+    let mut current_sha = "";
+    let mut current_line: u32 = 0;
+
+    for line in porcelain.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            if let Some(entry) = commits.get(current_sha) {
+                lines.push((current_line, content, entry.clone()));
+            }
+            continue;
+        }
+
+        if let Some(entry) = commits.get_mut(current_sha) {
+            if let Some(name) = line.strip_prefix("author ") {
+                entry.author = name.to_string();
+                continue;
+            }
+            if let Some(mail) = line.strip_prefix("author-mail ") {
+                entry.author_email = mail
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string();
+                continue;
+            }
+            if let Some(time) = line.strip_prefix("author-time ") {
+                entry.author_time = time.parse().unwrap_or(0);
+                continue;
+            }
+            if let Some(summary) = line.strip_prefix("summary ") {
+                entry.summary = summary.to_string();
+                continue;
+            }
+        }
+
+        // Line header: "<sha> <orig_line> <final_line> [<group_size>]"
+        let mut parts = line.split(' ');
+        if let (Some(sha), Some(_), Some(final_line)) = (parts.next(), parts.next(), parts.next()) {
+            if sha.len() == 40 && sha.chars().all(|c| c.is_ascii_hexdigit()) {
+                current_sha = sha;
+                current_line = final_line.parse().unwrap_or(0);
+                commits.entry(sha).or_insert_with(|| BlameEntry {
+                    sha: sha.to_string(),
+                    author: String::new(),
+                    author_email: String::new(),
+                    author_time: 0,
+                    summary: String::new(),
+                });
+            }
+        }
+    }
+
+    lines
+}
+
+/// Aggregate `git blame --porcelain` output into per-author line counts.
+/// Author headers only appear the first time a commit is seen, so they are
+/// remembered per commit SHA.
+fn parse_blame_author_stats(porcelain: &str) -> Vec<AuthorStats> {
+    let mut authors_by_sha: HashMap<&str, (&str, &str)> = HashMap::new();
+    let mut counts: HashMap<(&str, &str), u32> = HashMap::new();
+    let mut current_sha = "";
+    let mut total_lines: u32 = 0;
+
+    for line in porcelain.lines() {
+        if line.starts_with('\t') {
+            // Content line: attribute it to the current commit's author
+            if let Some(author) = authors_by_sha.get(current_sha) {
+                *counts.entry(*author).or_insert(0) += 1;
+                total_lines += 1;
+            }
+        } else if let Some(name) = line.strip_prefix("author ") {
+            let entry = authors_by_sha.entry(current_sha).or_insert(("", ""));
+            entry.0 = name;
+        } else if let Some(mail) = line.strip_prefix("author-mail ") {
+            let entry = authors_by_sha.entry(current_sha).or_insert(("", ""));
+            entry.1 = mail.trim_start_matches('<').trim_end_matches('>');
+        } else if let Some((sha, _)) = line.split_once(' ') {
+            if sha.len() == 40 && sha.chars().all(|c| c.is_ascii_hexdigit()) {
+                current_sha = sha;
+            }
+        }
+    }
+
+    let mut stats: Vec<AuthorStats> = counts
+        .into_iter()
+        .map(|((author, email), line_count)| AuthorStats {
+            author: author.to_string(),
+            email: email.to_string(),
+            line_count,
+            pct: if total_lines == 0 {
+                0.0
+            } else {
+                line_count as f64 / total_lines as f64
+            },
+        })
+        .collect();
+
+    stats.sort_by(|a, b| {
+        b.line_count
+            .cmp(&a.line_count)
+            .then_with(|| a.author.cmp(&b.author))
+    });
+    stats.truncate(MAX_BLAME_AUTHORS);
+    stats
+}
+
+/// Addition/deletion counts for a file without computing a full diff.
+/// Uses a cached diff when available; otherwise runs `git diff --numstat`
+/// and does not populate the cache.
+#[tauri::command]
+pub fn get_file_diff_stats_only(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    file_path: String,
+) -> Result<DiffStats, String> {
+    {
+        let key = cache_key(&repo_root, &base_sha, &head_sha, &file_path, false);
+        let mut cache = DIFF_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(cached) = cache.get(&key) {
+            return Ok(cached.stats.clone());
+        }
+    }
+
+    let is_working_tree = head_sha == "WORKING_TREE";
+    let mut args = vec!["diff".to_string(), "--numstat".to_string()];
+    if head_sha == "INDEX" {
+        args.push("--cached".to_string());
+        args.push(base_sha.clone());
+    } else if is_working_tree {
+        if base_sha != "INDEX" {
+            args.push(base_sha.clone());
+        }
+    } else {
+        args.push(format!("{}...{}", base_sha, head_sha));
+    }
+    args.push("--".to_string());
+    args.push(file_path.clone());
+
+    let output = Command::new("git")
+        .args(&args)
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to execute git diff: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git diff failed: {}", stderr));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if let Some(line) = stdout.lines().find(|line| !line.is_empty()) {
+        // "<added>\t<deleted>\t<path>", with "-" for binary files
+        let mut parts = line.split('\t');
+        let additions = parts.next().and_then(|n| n.parse().ok()).unwrap_or(0);
+        let deletions = parts.next().and_then(|n| n.parse().ok()).unwrap_or(0);
+        return Ok(DiffStats {
+            additions,
+            deletions,
+        });
+    }
+
+    // Untracked files don't show up in git diff; count them as all additions
+    let additions = if is_working_tree && !is_tracked(&repo_root, &file_path) {
+        get_file_from_working_tree(&repo_root, &file_path)
+            .map(|content| content.lines().count() as u32)
+            .unwrap_or(0)
+    } else {
+        0
+    };
+
+    Ok(DiffStats {
+        additions,
+        deletions: 0,
+    })
+}
+
+/// Search the diff lines of every file in a comparison. `scope` is "all",
+/// "added", or "deleted"; binary files and files whose diff fails are skipped.
+/// Diffs come from the diff cache where possible. Stops after
+/// `MAX_SEARCH_RESULTS` matches.
+#[tauri::command]
+pub async fn search_in_session(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    pattern: String,
+    case_sensitive: bool,
+    regex: bool,
+    scope: String,
+) -> Result<Vec<SearchResult>, String> {
+    // Diffing every file can take a while; keep it off the async runtime
+    tauri::async_runtime::spawn_blocking(move || {
+        search_session_diffs(
+            repo_root,
+            base_sha,
+            head_sha,
+            pattern,
+            case_sensitive,
+            regex,
+            scope,
+        )
+    })
+    .await
+    .map_err(|e| format!("Failed to search session: {}", e))?
+}
+
+fn search_session_diffs(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    pattern: String,
+    case_sensitive: bool,
+    regex: bool,
+    scope: String,
+) -> Result<Vec<SearchResult>, String> {
+    let line_type_filter = match scope.as_str() {
+        "all" => None,
+        "added" | "deleted" => Some(scope.as_str()),
+        _ => return Err(format!("Invalid search scope: {}", scope)),
+    };
+    if pattern.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let source = if regex {
+        pattern
+    } else {
+        regex::escape(&pattern)
+    };
+    let matcher = RegexBuilder::new(&source)
+        .case_insensitive(!case_sensitive)
+        .build()
+        .map_err(|e| format!("Invalid search pattern: {}", e))?;
+
+    let files = get_files_for_range(&repo_root, &base_sha, &head_sha)?;
+    let mut results = Vec::new();
+
+    for file in files.into_iter().filter(|f| !f.binary) {
+        // One unreadable file (e.g. deleted mid-search) shouldn't fail the search
+        let Ok(diff) = get_file_diff(
+            repo_root.clone(),
+            base_sha.clone(),
+            head_sha.clone(),
+            file.path.clone(),
+            false,
+            None,
+        ) else {
+            continue;
+        };
+
+        for (hunk_index, hunk) in diff.hunks.iter().enumerate() {
+            for line in &hunk.lines {
+                if line_type_filter.is_some_and(|t| t != line.line_type) {
+                    continue;
+                }
+                let line_num = if line.line_type == "deleted" {
+                    line.old_line_num
+                } else {
+                    line.new_line_num
+                };
+
+                for found in matcher.find_iter(&line.content) {
+                    results.push(SearchResult {
+                        file_path: file.path.clone(),
+                        hunk_index,
+                        line_num,
+                        content: line.content.clone(),
+                        match_start: found.start(),
+                        match_end: found.end(),
+                    });
+                    if results.len() >= MAX_SEARCH_RESULTS {
+                        return Ok(results);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+/// Get a path's mode at a ref. "INDEX" and "WORKING_TREE" both report the
+/// mode recorded in the index.
+#[tauri::command]
+pub fn get_file_mode(
+    repo_root: String,
+    ref_name: String,
+    file_path: String,
+) -> Result<FileModeInfo, String> {
+    let output = if ref_name == "INDEX" || ref_name == "WORKING_TREE" {
+        Command::new("git")
+            .args(["ls-files", "-s", "--", &file_path])
+            .current_dir(&repo_root)
+            .output()
+    } else {
+        Command::new("git")
+            .args(["ls-tree", &ref_name, "--", &file_path])
+            .current_dir(&repo_root)
+            .output()
+    }
+    .map_err(|e| format!("Failed to read file mode: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git ls-tree failed: {}", stderr.trim()));
+    }
+
+    // Both `ls-tree` and `ls-files -s` print the mode as the first column
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mode = stdout
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| format!("File not found at {}: {}", ref_name, file_path))?
+        .to_string();
+
+    Ok(FileModeInfo {
+        is_executable: mode == "100755",
+        is_symlink: mode == "120000",
+        is_submodule: mode == "160000",
+        mode,
+    })
+}
+
+/// Extract the conflict blocks from a working tree file for the resolution UI.
+/// With `merge.conflictStyle=diff3`, the `|||||||` base section is left out of `ours_content`.
+#[tauri::command]
+pub fn get_conflict_sections(
+    repo_root: String,
+    file_path: String,
+) -> Result<Vec<ConflictSection>, String> {
+    let content = get_file_from_working_tree(&repo_root, &file_path)?;
+    let language = detect_language_from_path(&file_path);
+
+    let mut sections = parse_conflict_sections(&content);
+    for section in &mut sections {
+        section.ours_highlights = highlight_file_lines(&section.ours_content, &language);
+        section.theirs_highlights = highlight_file_lines(&section.theirs_content, &language);
+    }
+    Ok(sections)
+}
+
+/// Scan for conflict markers. Unterminated blocks are ignored.
+fn parse_conflict_sections(content: &str) -> Vec<ConflictSection> {
+    let mut sections = Vec::new();
+    let mut start_line = None;
+    let mut base_line = None;
+    let mut mid_line = None;
+    let mut ours: Vec<&str> = Vec::new();
+    let mut theirs: Vec<&str> = Vec::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        let line_num = idx as u32 + 1;
+        if line.starts_with("<<<<<<<") {
+            start_line = Some(line_num);
+            base_line = None;
+            mid_line = None;
+            ours.clear();
+            theirs.clear();
+        } else if start_line.is_some() && mid_line.is_none() && line.starts_with("|||||||") {
+            base_line = Some(line_num);
+        } else if start_line.is_some() && mid_line.is_none() && line.starts_with("=======") {
+            mid_line = Some(line_num);
+        } else if line.starts_with(">>>>>>>") {
+            if let (Some(start), Some(mid)) = (start_line, mid_line) {
+                sections.push(ConflictSection {
+                    start_line: start,
+                    mid_line: mid,
+                    end_line: line_num,
+                    ours_content: ours.join("\n"),
+                    theirs_content: theirs.join("\n"),
+                    ours_highlights: Vec::new(),
+                    theirs_highlights: Vec::new(),
+                });
+            }
+            start_line = None;
+        } else if start_line.is_some() {
+            if mid_line.is_some() {
+                theirs.push(line);
+            } else if base_line.is_none() {
+                ours.push(line);
+            }
+        }
+    }
+
+    sections
+}
+
+/// Describe how a submodule's recorded commit moved between two refs. Commit
+/// counts need the submodule checked out; otherwise only the SHAs are returned.
+#[tauri::command]
+pub fn get_submodule_diff(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    submodule_path: String,
+) -> Result<SubmoduleDiff, String> {
+    let old_sha = get_gitlink_sha(&repo_root, &base_sha, &submodule_path)?.unwrap_or_default();
+    let new_sha = get_gitlink_sha(&repo_root, &head_sha, &submodule_path)?.unwrap_or_default();
+    if old_sha.is_empty() && new_sha.is_empty() {
+        return Err(format!("Not a submodule: {}", submodule_path));
+    }
+
+    let submodule_url = get_submodule_url(&repo_root, &submodule_path);
+
+    let submodule_dir = std::path::Path::new(&repo_root).join(&submodule_path);
+    let initialized = submodule_dir.join(".git").exists();
+    let (commits_removed, commits_added) =
+        if initialized && !old_sha.is_empty() && !new_sha.is_empty() {
+            count_left_right(&submodule_dir, &old_sha, &new_sha).unwrap_or((0, 0))
+        } else {
+            (0, 0)
+        };
+
+    Ok(SubmoduleDiff {
+        old_sha,
+        new_sha,
+        submodule_url,
+        commits_added,
+        commits_removed,
+    })
+}
+
+/// Commit a submodule (gitlink) points to at a ref, the index, or in the
+/// working tree (the submodule's checked-out HEAD). None if it doesn't exist there.
+fn get_gitlink_sha(repo_root: &str, sha: &str, path: &str) -> Result<Option<String>, String> {
+    // An uninitialized submodule has no checkout; `rev-parse` there would resolve
+    // the parent repository's HEAD, so fall back to the recorded commit
+    let submodule_git = std::path::Path::new(repo_root).join(path).join(".git");
+    let sha = if sha == "WORKING_TREE" && !submodule_git.exists() {
+        "INDEX"
+    } else {
+        sha
+    };
+
+    let output = match sha {
+        "WORKING_TREE" => Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(std::path::Path::new(repo_root).join(path))
+            .output(),
+        "INDEX" => Command::new("git")
+            .args(["ls-files", "-s", "--", path])
+            .current_dir(repo_root)
+            .output(),
+        _ => Command::new("git")
+            .args(["ls-tree", sha, "--", path])
+            .current_dir(repo_root)
+            .output(),
+    }
+    .map_err(|e| format!("Failed to resolve submodule commit: {}", e))?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if sha == "WORKING_TREE" {
+        let head = stdout.trim();
+        return Ok((!head.is_empty()).then(|| head.to_string()));
+    }
+
+    // `ls-tree`: "160000 commit <sha>\t<path>"; `ls-files -s`: "160000 <sha> 0\t<path>"
+    Ok(stdout.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        if fields.next()? != "160000" {
+            return None;
+        }
+        fields.find(|f| f.len() >= 40).map(str::to_string)
+    }))
+}
+
+/// Look up a submodule's URL in .gitmodules by its path
+fn get_submodule_url(repo_root: &str, submodule_path: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args([
+            "config",
+            "-f",
+            ".gitmodules",
+            "--get-regexp",
+            r"^submodule\..*\.path$",
+        ])
+        .current_dir(repo_root)
+        .output()
+        .ok()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let name = stdout.lines().find_map(|line| {
+        let (key, path) = line.split_once(' ')?;
+        (path == submodule_path).then(|| {
+            key.trim_start_matches("submodule.")
+                .trim_end_matches(".path")
+                .to_string()
+        })
+    })?;
+
+    let output = Command::new("git")
+        .args([
+            "config",
+            "-f",
+            ".gitmodules",
+            "--get",
+            &format!("submodule.{}.url", name),
+        ])
+        .current_dir(repo_root)
+        .output()
+        .ok()?;
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!url.is_empty()).then_some(url)
+}
+
+/// `git rev-list --left-right --count old...new` in `dir`: (only in old, only in new)
+fn count_left_right(dir: &std::path::Path, old_sha: &str, new_sha: &str) -> Option<(u32, u32)> {
+    let output = Command::new("git")
+        .args([
+            "rev-list",
+            "--left-right",
+            "--count",
+            &format!("{}...{}", old_sha, new_sha),
+        ])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut counts = stdout.split_whitespace().map(|n| n.parse().ok());
+    Some((counts.next()??, counts.next()??))
+}
+
+/// Check whether git tracks a path (is in the index)
+fn is_tracked(repo_root: &str, file_path: &str) -> bool {
+    Command::new("git")
+        .args(["ls-files", "--", file_path])
+        .current_dir(repo_root)
+        .output()
+        .map(|output| !output.stdout.is_empty())
+        .unwrap_or(false)
+}
+
+/// Locate an old- or new-side line number in a diff, e.g. to scroll to it
+/// after following a blame link. `side` is "old" or "new".
+#[tauri::command]
+pub fn find_line_in_diff(diff: FileDiff, line_num: u32, side: String) -> Option<LineLocation> {
+    let use_old = match side.as_str() {
+        "old" => true,
+        "new" => false,
+        _ => return None,
+    };
+
+    diff.hunks
+        .iter()
+        .enumerate()
+        .find_map(|(hunk_index, hunk)| {
+            hunk.lines
+                .iter()
+                .position(|line| {
+                    let num = if use_old {
+                        line.old_line_num
+                    } else {
+                        line.new_line_num
+                    };
+                    num == Some(line_num)
+                })
+                .map(|line_index_in_hunk| LineLocation {
+                    hunk_index,
+                    line_index_in_hunk,
+                })
+        })
+}
+
+/// Fetch highlighted lines `start_line..=end_line` (1-based) of a file at a ref,
+/// e.g. to show the function signature above a hunk
+#[tauri::command]
+pub fn get_file_context_range(
+    repo_root: String,
+    ref_name: String,
+    file_path: String,
+    start_line: u32,
+    end_line: u32,
+) -> Result<Vec<ContextLine>, String> {
+    if start_line == 0 || start_line > end_line {
+        return Err(format!("Invalid line range {}-{}", start_line, end_line));
+    }
+    if end_line - start_line > MAX_CONTEXT_LINES {
+        return Err(format!(
+            "Line range too large (max {} lines)",
+            MAX_CONTEXT_LINES
+        ));
+    }
+
+    let content = if ref_name == "WORKING_TREE" {
+        get_file_from_working_tree(&repo_root, &file_path)?
+    } else {
+        get_file_at_ref(&repo_root, object_ref(&ref_name), &file_path)?
+    };
+
+    // Highlight the whole file so the range gets correct multi-line context
+    let language = detect_language_from_path(&file_path);
+    let mut line_highlights = highlight_file_lines(&content, &language);
+
+    Ok(content
+        .lines()
+        .enumerate()
+        .skip((start_line - 1) as usize)
+        .take((end_line - start_line + 1) as usize)
+        .map(|(idx, line)| ContextLine {
+            line_num: idx as u32 + 1,
+            content: line.to_string(),
+            highlights: line_highlights
+                .get_mut(idx)
+                .map(std::mem::take)
+                .unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Compare a binary file's size between two refs
+#[tauri::command]
+pub fn get_binary_size_diff(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    file_path: String,
+) -> Result<BinarySizeDiff, String> {
+    let old_size = get_size_at(&repo_root, &base_sha, &file_path);
+    let new_size = get_size_at(&repo_root, &head_sha, &file_path);
+    if old_size.is_none() && new_size.is_none() {
+        return Err(format!("File not found at either ref: {}", file_path));
+    }
+
+    let delta_bytes = new_size.unwrap_or(0) as i64 - old_size.unwrap_or(0) as i64;
+    let delta_pct = old_size
+        .filter(|&old| old > 0)
+        .map(|old| delta_bytes as f64 / old as f64 * 100.0);
+
+    Ok(BinarySizeDiff {
+        old_size,
+        new_size,
+        delta_bytes,
+        delta_pct,
+    })
+}
+
+/// Report a file's line endings at a ref (or "WORKING_TREE"), to spot mixed
+/// CRLF/LF content behind spurious whole-line changes
+#[tauri::command]
+pub fn get_line_ending_info(
+    repo_root: String,
+    ref_name: String,
+    file_path: String,
+) -> Result<LineEndingInfo, String> {
+    let content = if ref_name == "WORKING_TREE" {
+        get_file_from_working_tree(&repo_root, &file_path)?
+    } else {
+        get_file_at_ref(&repo_root, object_ref(&ref_name), &file_path)?
+    };
+    Ok(analyze_line_endings(&content))
+}
+
+fn analyze_line_endings(content: &str) -> LineEndingInfo {
+    let bytes = content.as_bytes();
+    let mut crlf_count = 0u32;
+    let mut lf_count = 0u32;
+    for (i, &b) in bytes.iter().enumerate() {
+        if b == b'\n' {
+            if i > 0 && bytes[i - 1] == b'\r' {
+                crlf_count += 1;
+            } else {
+                lf_count += 1;
+            }
+        }
+    }
+
+    let has_crlf = crlf_count > 0;
+    let has_lf = lf_count > 0;
+    let detected_ending = match (has_crlf, has_lf) {
+        (true, true) => "mixed",
+        (true, false) => "crlf",
+        (false, true) => "lf",
+        (false, false) => "none",
+    };
+
+    LineEndingInfo {
+        has_crlf,
+        has_lf,
+        is_mixed: has_crlf && has_lf,
+        crlf_count,
+        lf_count,
+        detected_ending: detected_ending.to_string(),
+    }
+}
+
+/// Convert every line's highlight offsets from UTF-8 bytes to UTF-16 code units
+/// so the frontend can slice line content with them directly
+#[tauri::command]
+pub fn normalize_diff_spans(mut diff: FileDiff) -> FileDiff {
+    for line in diff.hunks.iter_mut().flat_map(|hunk| hunk.lines.iter_mut()) {
+        let highlights = std::mem::take(&mut line.highlights);
+        line.highlights = normalize_spans(&line.content, highlights);
+    }
+    diff
+}
+
+/// Estimate the tokens needed to send a diff's added and deleted lines to an
+/// AI model, using the common ~4 characters per token heuristic
+#[tauri::command]
+pub fn get_diff_token_count(diff: FileDiff) -> Result<TokenCountEstimate, String> {
+    let char_count: usize = diff
+        .hunks
+        .iter()
+        .flat_map(|hunk| &hunk.lines)
+        .filter(|line| line.line_type == "added" || line.line_type == "deleted")
+        .map(|line| line.content.chars().count())
+        .sum();
+    let estimated_tokens = char_count.div_ceil(4) as u32;
+
+    Ok(TokenCountEstimate {
+        char_count: char_count as u32,
+        estimated_tokens,
+        fits_in_4k: estimated_tokens <= 4_096,
+        fits_in_16k: estimated_tokens <= 16_384,
+        fits_in_128k: estimated_tokens <= 131_072,
+    })
+}
+
+/// Count word-level changes in a diff. Lines carrying word-level highlights
+/// (modified lines) contribute only the words inside their changed ranges.
+#[tauri::command]
+pub fn get_diff_word_stats(diff: FileDiff) -> WordStats {
+    let mut stats = WordStats {
+        added_words: 0,
+        deleted_words: 0,
+        changed_words: 0,
+    };
+
+    for line in diff.hunks.iter().flat_map(|hunk| &hunk.lines) {
+        let word_scope = match line.line_type.as_str() {
+            "added" => "word-added",
+            "deleted" => "word-deleted",
+            _ => continue,
+        };
+
+        let word_ranges: Vec<(usize, usize)> = line
+            .highlights
+            .iter()
+            .filter(|span| span.scope == word_scope)
+            .map(|span| (span.start as usize, span.end as usize))
+            .collect();
+
+        if !word_ranges.is_empty() {
+            stats.changed_words += count_words_in_ranges(&line.content, &word_ranges);
+        } else if line.line_type == "added" {
+            stats.added_words += line.content.split_whitespace().count() as u32;
+        } else {
+            stats.deleted_words += line.content.split_whitespace().count() as u32;
+        }
+    }
+
+    stats
+}
+
+/// Count whitespace-separated words that overlap any of the byte ranges
+fn count_words_in_ranges(content: &str, ranges: &[(usize, usize)]) -> u32 {
+    let mut count = 0;
+    let mut word_start = None;
+
+    for (i, c) in content
+        .char_indices()
+        .chain(std::iter::once((content.len(), ' ')))
+    {
+        match (c.is_whitespace(), word_start) {
+            (false, None) => word_start = Some(i),
+            (true, Some(start)) => {
+                if ranges.iter().any(|&(s, e)| s < i && start < e) {
+                    count += 1;
+                }
+                word_start = None;
+            }
+            _ => {}
+        }
+    }
+
+    count
+}
+
+/// Get a file's content at a ref along with per-line syntax highlighting.
+/// Supports the "WORKING_TREE" and "INDEX" pseudo-refs; only commits are cached.
+#[tauri::command]
+pub fn get_highlighted_file(
+    repo_root: String,
+    ref_name: String,
+    file_path: String,
+) -> Result<HighlightedFile, String> {
+    let (content, cache_key) = if ref_name == "WORKING_TREE" {
+        (get_file_from_working_tree(&repo_root, &file_path)?, None)
+    } else if ref_name == "INDEX" {
+        (
+            get_file_at_ref(&repo_root, object_ref(&ref_name), &file_path)?,
+            None,
+        )
+    } else {
+        let commit_sha = resolve_ref(&repo_root, &ref_name)?;
+        let key = format!("{}:{}:{}", repo_root, commit_sha, file_path);
+        {
+            let mut cache = HIGHLIGHTED_FILE_CACHE
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            if let Some(cached) = cache.get(&key) {
+                return Ok(cached.clone());
+            }
+        }
+        (
+            get_file_at_ref(&repo_root, &commit_sha, &file_path)?,
+            Some(key),
+        )
+    };
+
+    let language = detect_language_from_path(&file_path);
+    let mut line_highlights = highlight_file_lines(&content, &language);
+    let lines = content
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| HighlightedLine {
+            content: line.to_string(),
+            line_num: idx as u32 + 1,
+            highlights: line_highlights
+                .get_mut(idx)
+                .map(std::mem::take)
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    let file = HighlightedFile {
+        encoding: detect_encoding(&content).to_string(),
+        content,
+        lines,
+        language,
+    };
+
+    if let Some(key) = cache_key {
+        let mut cache = HIGHLIGHTED_FILE_CACHE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        cache.put(key, file.clone());
+    }
+
+    Ok(file)
+}
+
+/// Count total, blank, comment, and code lines for a file at a ref
+#[tauri::command]
+pub fn get_file_stats(
+    repo_root: String,
+    ref_name: String,
+    file_path: String,
+) -> Result<FileCodeStats, String> {
+    let content = get_file_at_ref(&repo_root, &ref_name, &file_path)?;
+    let size_bytes =
+        get_blob_size(&repo_root, &ref_name, &file_path).unwrap_or(content.len() as u64);
+
+    let mut stats = count_code_lines(&content);
+    stats.language = detect_language_from_path(&file_path);
+    stats.encoding = detect_encoding(&content).to_string();
+    stats.size_bytes = size_bytes;
+    Ok(stats)
+}
+
+/// Heuristic line classification: blank, comment (by prefix), or code
+fn count_code_lines(content: &str) -> FileCodeStats {
+    let mut stats = FileCodeStats {
+        total_lines: 0,
+        blank_lines: 0,
+        comment_lines: 0,
+        code_lines: 0,
+        language: String::new(),
+        encoding: String::new(),
+        size_bytes: 0,
+    };
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        stats.total_lines += 1;
+        if trimmed.is_empty() {
+            stats.blank_lines += 1;
+        } else if COMMENT_PREFIXES.iter().any(|p| trimmed.starts_with(p)) {
+            stats.comment_lines += 1;
+        } else {
+            stats.code_lines += 1;
+        }
+    }
+
+    stats
+}
+
+/// Best-effort encoding label. Content is decoded lossily, so invalid UTF-8
+/// shows up as replacement characters.
+fn detect_encoding(content: &str) -> &'static str {
+    if content.starts_with('\u{feff}') {
+        "utf-8-bom"
+    } else if content.contains('\u{fffd}') {
+        "unknown"
+    } else {
+        "utf-8"
+    }
+}
+
+/// Get the stable `git patch-id` of one file's changes in `base_sha...head_sha`
+/// (or against the working tree / index for those pseudo-refs).
+/// The ID ignores line numbers and whitespace, so the same change rebased onto
+/// different commits gets the same ID.
+#[tauri::command]
+pub fn get_patch_id(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    file_path: String,
+) -> Result<String, String> {
+    let diff = Command::new("git")
+        .arg("diff")
+        .args(diff_range_args(&base_sha, &head_sha))
+        .args(["--", &file_path])
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to execute git diff: {}", e))?;
+
+    if !diff.status.success() {
+        let stderr = String::from_utf8_lossy(&diff.stderr);
+        return Err(format!("git diff failed: {}", stderr.trim()));
+    }
+    if diff.stdout.is_empty() {
+        return Err(format!("No changes to {}", file_path));
+    }
+
+    let mut child = Command::new("git")
+        .args(["patch-id", "--stable"])
+        .current_dir(&repo_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to execute git patch-id: {}", e))?;
+
+    // Dropping stdin after the write closes it so patch-id sees EOF
+    child
+        .stdin
+        .take()
+        .ok_or("Failed to open git patch-id stdin")?
+        .write_all(&diff.stdout)
+        .map_err(|e| format!("Failed to write to git patch-id: {}", e))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to read git patch-id output: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git patch-id failed: {}", stderr.trim()));
+    }
+
+    // Output: "<patch-id> <commit-id>" (commit-id is all zeros for a bare diff)
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .next()
+        .map(|id| id.to_string())
+        .ok_or_else(|| format!("git patch-id produced no ID for {}", file_path))
+}
+
+/// Summarize a whole comparison as files changed, insertions, and deletions.
+/// "WORKING_TREE" and "INDEX" heads are supported but not cached.
+#[tauri::command]
+pub fn get_shortstat_summary(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+) -> Result<ShortstatSummary, String> {
+    let is_commit_pair =
+        !matches!(head_sha.as_str(), "WORKING_TREE" | "INDEX") && base_sha != "INDEX";
+    let key = format!("{}:{}:{}", repo_root, base_sha, head_sha);
+    if is_commit_pair {
+        let mut cache = SHORTSTAT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(cached) = cache.get(&key) {
+            return Ok(cached.clone());
+        }
+    }
+
+    let mut args = vec!["diff".to_string(), "--shortstat".to_string()];
+    match (base_sha.as_str(), head_sha.as_str()) {
+        ("INDEX", "WORKING_TREE") => {}
+        (base, "WORKING_TREE") => args.push(base.to_string()),
+        (base, "INDEX") => args.extend(["--cached".to_string(), base.to_string()]),
+        (base, head) => args.push(format!("{}...{}", base, head)),
+    }
 
-        if ignore_whitespace {
-            args.insert(1, "-w".to_string());
-        }
+    let output = Command::new("git")
+        .args(&args)
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to execute git diff: {}", e))?;
 
-        let output = Command::new("git")
-            .args(&args)
-            .current_dir(&repo_root)
-            .output()
-            .map_err(|e| format!("Failed to execute git diff: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git diff failed: {}", stderr.trim()));
+    }
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("git diff failed: {}", stderr));
-        }
+    let summary = parse_shortstat(&String::from_utf8_lossy(&output.stdout));
+    if is_commit_pair {
+        let mut cache = SHORTSTAT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        cache.put(key, summary.clone());
+    }
+    Ok(summary)
+}
 
-        String::from_utf8_lossy(&output.stdout).into_owned()
+/// Parse ` 5 files changed, 42 insertions(+), 17 deletions(-)`. Git omits zero
+/// counts, and prints nothing at all when there are no changes.
+fn parse_shortstat(output: &str) -> ShortstatSummary {
+    let mut summary = ShortstatSummary {
+        files_changed: 0,
+        insertions: 0,
+        deletions: 0,
+        formatted: String::new(),
     };
 
-    // Detect language for syntax highlighting
-    let language = detect_language_from_path(&file_path);
+    for part in output.trim().split(',') {
+        let mut words = part.split_whitespace();
+        let Some(count) = words.next().and_then(|n| n.parse().ok()) else {
+            continue;
+        };
+        match words.next() {
+            Some(w) if w.starts_with("file") => summary.files_changed = count,
+            Some(w) if w.starts_with("insertion") => summary.insertions = count,
+            Some(w) if w.starts_with("deletion") => summary.deletions = count,
+            _ => {}
+        }
+    }
 
-    // Get file content for syntax highlighting context
-    let head_content = if is_working_tree {
-        // Read current file from working tree
-        get_file_from_working_tree(&repo_root, &file_path).ok()
-    } else {
-        get_file_at_ref(&repo_root, &head_sha, &file_path).ok()
-    };
+    let plural = |n: u32| if n == 1 { "" } else { "s" };
+    summary.formatted = format!(
+        "{} file{} changed, {} insertion{}(+), {} deletion{}(-)",
+        summary.files_changed,
+        plural(summary.files_changed),
+        summary.insertions,
+        plural(summary.insertions),
+        summary.deletions,
+        plural(summary.deletions)
+    );
+    summary
+}
 
-    // Get file content at base for deleted lines
-    let base_content = get_file_at_ref(&repo_root, &base_sha, &file_path).ok();
+/// Get files changed, insertions, and deletions for each commit, in input order.
+/// Uncached commits are computed in parallel.
+#[tauri::command]
+pub fn get_commits_with_stats(
+    repo_root: String,
+    commits: Vec<String>,
+) -> Result<Vec<CommitWithStats>, String> {
+    commits
+        .into_par_iter()
+        .map(|sha| {
+            // Only full SHAs are cached; refs and short SHAs can move or become ambiguous
+            let cacheable = sha.len() == 40 && sha.bytes().all(|b| b.is_ascii_hexdigit());
+            let key = format!("{}:{}", repo_root, sha);
+            if cacheable {
+                let mut cache = COMMIT_STATS_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(cached) = cache.get(&key) {
+                    return Ok(cached.clone());
+                }
+            }
 
-    // Check if this is a new file (no base content and empty diff but head content exists)
-    let (hunks, stats, content_hash) =
-        if diff_content.trim().is_empty() && base_content.is_none() && head_content.is_some() {
-            // New file: generate synthetic diff showing all lines as additions
-            let file_content = head_content.as_deref().unwrap();
-            let content_hash = compute_hash(file_content);
-            let (hunks, stats) = generate_new_file_diff(file_content, &language);
-            (hunks, stats, content_hash)
-        } else if diff_content.trim().is_empty() && head_content.is_none() && base_content.is_some()
-        {
-            // Deleted file: generate synthetic diff showing all lines as deletions
-            let file_content = base_content.as_deref().unwrap();
-            let content_hash = compute_hash(file_content);
-            let (hunks, stats) = generate_deleted_file_diff(file_content, &language);
-            (hunks, stats, content_hash)
-        } else {
-            // Normal diff: parse the git diff output
-            let content_hash = compute_hash(&diff_content);
-            let (hunks, stats) = parse_diff_with_highlights(
-                &diff_content,
-                &language,
-                head_content.as_deref(),
-                base_content.as_deref(),
-            );
-            (hunks, stats, content_hash)
-        };
+            let output = Command::new("git")
+                .args(["show", "--stat", "--format=", &sha])
+                .current_dir(&repo_root)
+                .output()
+                .map_err(|e| format!("Failed to execute git show: {}", e))?;
 
-    let diff = FileDiff {
-        path: file_path,
-        hunks,
-        content_hash,
-        stats,
-    };
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(format!("git show {} failed: {}", sha, stderr.trim()));
+            }
 
-    // Store in cache (only for commit-to-commit diffs)
-    if !is_working_tree {
-        let mut cache = DIFF_CACHE.lock().unwrap_or_else(|e| e.into_inner());
-        cache.put(key, diff.clone());
-    }
+            // The last line of `--stat` is the shortstat summary
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let totals = parse_shortstat(stdout.lines().last().unwrap_or(""));
+            let stats = CommitWithStats {
+                sha,
+                files_changed: totals.files_changed,
+                insertions: totals.insertions,
+                deletions: totals.deletions,
+            };
 
-    Ok(diff)
+            if cacheable {
+                let mut cache = COMMIT_STATS_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+                cache.put(key, stats.clone());
+            }
+            Ok(stats)
+        })
+        .collect()
 }
 
 /// Invalidate cache entries for a specific repository
@@ -219,11 +2346,78 @@ pub fn invalidate_diff_cache(repo_root: String) {
     }
 }
 
+/// Invalidate cache entries for a single file in a repository.
+/// Returns the number of entries evicted.
+#[tauri::command]
+pub fn invalidate_file_diff_cache(repo_root: String, file_path: String) -> Result<u32, String> {
+    Ok(evict_file_from_cache(repo_root, file_path))
+}
+
+/// List cached diffs for a repository, sorted by file path
+#[tauri::command]
+pub fn get_diff_cache_entries(repo_root: String) -> Result<Vec<CacheEntry>, String> {
+    let cache = DIFF_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let prefix = format!("{}:", repo_root);
+
+    let mut entries: Vec<CacheEntry> = cache
+        .iter()
+        .filter_map(|(key, diff)| {
+            // Key layout: see `cache_key`. SHAs never contain ':', file paths might.
+            let mut parts = key.strip_prefix(&prefix)?.splitn(3, ':');
+            let base_sha = parts.next()?.to_string();
+            let head_sha = parts.next()?.to_string();
+            Some(CacheEntry {
+                file_path: diff.path.clone(),
+                base_sha,
+                head_sha,
+                size_bytes: serde_json::to_string(diff).map(|s| s.len()).unwrap_or(0),
+            })
+        })
+        .collect();
+    entries.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+
+    Ok(entries)
+}
+
+/// Remove every cached diff of a file, regardless of SHA pair.
+/// Returns the number of entries evicted.
+#[tauri::command]
+pub fn evict_file_from_cache(repo_root: String, file_path: String) -> u32 {
+    let mut cache = DIFF_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let prefix = format!("{}:", repo_root);
+    let keys_to_remove: Vec<String> = cache
+        .iter()
+        .filter(|(k, diff)| k.starts_with(&prefix) && diff.path == file_path)
+        .map(|(k, _)| k.clone())
+        .collect();
+
+    for key in &keys_to_remove {
+        cache.pop(key);
+    }
+
+    keys_to_remove.len() as u32
+}
+
+/// Resize the diff cache; shrinking evicts the least recently used diffs.
+/// The cache is shared by all repos, so the most recently applied size wins.
+pub fn set_diff_cache_capacity(capacity: usize) {
+    if let Some(capacity) = NonZeroUsize::new(capacity) {
+        DIFF_CACHE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .resize(capacity);
+    }
+}
+
 /// Clear entire diff cache
 #[tauri::command]
 pub fn clear_diff_cache() {
     let mut cache = DIFF_CACHE.lock().unwrap_or_else(|e| e.into_inner());
     cache.clear();
+    WORD_REGEX_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
 }
 
 #[tauri::command]
@@ -231,6 +2425,94 @@ pub fn compute_content_hash(content: String) -> String {
     compute_hash(&content)
 }
 
+/// Hash a file's changes rather than its content: two sessions with different
+/// SHAs but the same edits to this file produce the same hash
+#[tauri::command]
+pub fn compute_session_content_hash(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    file_path: String,
+) -> Result<String, String> {
+    let mut args = vec![
+        "diff".to_string(),
+        "--no-color".to_string(),
+        "--no-ext-diff".to_string(),
+    ];
+    args.extend(diff_range_args(&base_sha, &head_sha));
+    args.extend(["--".to_string(), file_path.clone()]);
+
+    let output = Command::new("git")
+        .args(&args)
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to execute git diff: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git diff failed: {}", stderr));
+    }
+
+    let diff = String::from_utf8_lossy(&output.stdout);
+
+    // Untracked files produce no diff; hash the all-added diff they'll have once committed
+    if diff.trim().is_empty()
+        && head_sha == "WORKING_TREE"
+        && get_file_at_ref(&repo_root, object_ref(&base_sha), &file_path).is_err()
+    {
+        if let Ok(content) = get_file_from_working_tree(&repo_root, &file_path) {
+            return Ok(compute_hash(&added_file_canonical_diff(&content)));
+        }
+    }
+
+    Ok(compute_hash(&canonical_diff(&diff)))
+}
+
+/// Strip everything SHA- or position-dependent from a diff (file headers,
+/// blob ids, hunk line numbers), keeping only the changed and context lines
+fn canonical_diff(diff: &str) -> String {
+    let mut canonical = String::new();
+    let mut in_hunk = false;
+
+    for line in diff.lines() {
+        if line.starts_with("@@") {
+            in_hunk = true;
+            canonical.push_str("@@\n");
+        } else if in_hunk
+            && (line.starts_with('+')
+                || line.starts_with('-')
+                || line.starts_with(' ')
+                || line.starts_with('\\'))
+        {
+            canonical.push_str(line);
+            canonical.push('\n');
+        } else if line.starts_with("diff --git") {
+            in_hunk = false;
+        }
+    }
+
+    canonical
+}
+
+/// `canonical_diff` of a diff adding `content` as a new file
+fn added_file_canonical_diff(content: &str) -> String {
+    if content.is_empty() {
+        return String::new();
+    }
+
+    let mut canonical = String::from("@@\n");
+    for line in content.lines() {
+        canonical.push('+');
+        canonical.push_str(line);
+        canonical.push('\n');
+    }
+    if !content.ends_with('\n') {
+        canonical.push_str("\\ No newline at end of file\n");
+    }
+
+    canonical
+}
+
 fn compute_hash(content: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(content.as_bytes());
@@ -238,6 +2520,53 @@ fn compute_hash(content: &str) -> String {
     format!("{:x}", result)
 }
 
+/// Map the "INDEX" pseudo-ref to git's `:<path>` index syntax (an empty ref)
+fn object_ref(sha: &str) -> &str {
+    if sha == "INDEX" {
+        ""
+    } else {
+        sha
+    }
+}
+
+/// Revision arguments for `git diff` between two refs. A WORKING_TREE head
+/// diffs against the working tree and an INDEX head uses `--cached`; an INDEX
+/// base takes no revision argument.
+fn diff_range_args(base_sha: &str, head_sha: &str) -> Vec<String> {
+    match (base_sha, head_sha) {
+        ("INDEX", "WORKING_TREE") => Vec::new(),
+        (base, "WORKING_TREE") => vec![base.to_string()],
+        ("INDEX", "INDEX") => vec!["--cached".to_string()],
+        (base, "INDEX") => vec!["--cached".to_string(), base.to_string()],
+        (base, head) => vec![format!("{}...{}", base, head)],
+    }
+}
+
+/// Base-side path of `file_path` if git detects it as a rename at `threshold`%
+fn find_rename_source(
+    repo_root: &str,
+    base_sha: &str,
+    head_sha: &str,
+    file_path: &str,
+    threshold: u8,
+) -> Option<String> {
+    let output = Command::new("git")
+        .args(["diff", "--name-status", &find_renames_arg(Some(threshold))])
+        .args(diff_range_args(base_sha, head_sha))
+        .current_dir(repo_root)
+        .output()
+        .ok()?;
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| {
+            let mut parts = line.split('\t');
+            let status = parts.next()?;
+            let (old, new) = (parts.next()?, parts.next()?);
+            (status.starts_with('R') && new == file_path).then(|| old.to_string())
+        })
+}
+
 /// Get file content at a specific git ref
 fn get_file_at_ref(repo_root: &str, ref_name: &str, file_path: &str) -> Result<String, String> {
     let output = Command::new("git")
@@ -269,13 +2598,91 @@ fn get_file_from_working_tree(repo_root: &str, file_path: &str) -> Result<String
         .map_err(|e| format!("Failed to read file from working tree: {}", e))
 }
 
+/// Get the size in bytes of a file at a git ref via `git cat-file -s`
+fn get_blob_size(repo_root: &str, ref_name: &str, file_path: &str) -> Option<u64> {
+    let output = Command::new("git")
+        .args(["cat-file", "-s", &format!("{}:{}", ref_name, file_path)])
+        .current_dir(repo_root)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+/// Size of a file at a commit, the index, or the working tree
+fn get_size_at(repo_root: &str, sha: &str, file_path: &str) -> Option<u64> {
+    if sha == "WORKING_TREE" {
+        std::fs::metadata(std::path::Path::new(repo_root).join(file_path))
+            .ok()
+            .map(|m| m.len())
+    } else {
+        get_blob_size(repo_root, object_ref(sha), file_path)
+    }
+}
+
+/// Check for git's "Binary files ... differ" marker
+fn is_binary_diff(diff: &str) -> bool {
+    diff.lines()
+        .any(|line| line.starts_with("Binary files ") && line.ends_with(" differ"))
+}
+
+/// Format a byte count for display, e.g. "12.4 KB"
+fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["KB", "MB", "GB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Summary line for a text diff, e.g. "+42 −17 in 3 hunks"
+fn text_summary(stats: &DiffStats, hunk_count: usize) -> String {
+    if hunk_count == 0 {
+        return "No changes".to_string();
+    }
+    format!(
+        "+{} \u{2212}{} in {} {}",
+        stats.additions,
+        stats.deletions,
+        hunk_count,
+        if hunk_count == 1 { "hunk" } else { "hunks" }
+    )
+}
+
+/// Summary line for a binary diff, e.g. "Binary file changed (12.4 KB → 15.1 KB)"
+fn binary_summary(old_size: Option<u64>, new_size: Option<u64>) -> String {
+    match (old_size, new_size) {
+        (Some(old), Some(new)) => format!(
+            "Binary file changed ({} \u{2192} {})",
+            format_size(old),
+            format_size(new)
+        ),
+        (None, Some(new)) => format!("Binary file added ({})", format_size(new)),
+        (Some(old), None) => format!("Binary file deleted ({})", format_size(old)),
+        (None, None) => "Binary file changed".to_string(),
+    }
+}
+
 /// Parse diff with syntax highlighting applied to each line
+/// Returns the hunks, their stats, and a one-line summary
 fn parse_diff_with_highlights(
     diff: &str,
     language: &str,
     head_content: Option<&str>,
     base_content: Option<&str>,
-) -> (Vec<Hunk>, DiffStats) {
+    word_regex: Option<&Regex>,
+) -> (Vec<Hunk>, DiffStats, String) {
     // Pre-compute highlights for entire files (gives Tree-sitter full context)
     let head_highlights: Vec<Vec<HighlightSpan>> = head_content
         .map(|c| highlight_file_lines(c, language))
@@ -290,8 +2697,22 @@ fn parse_diff_with_highlights(
     let mut new_line_num: u32 = 0;
     let mut total_additions: u32 = 0;
     let mut total_deletions: u32 = 0;
+    let mut renamed_from: Option<&str> = None;
+    let mut renamed_to: Option<&str> = None;
 
     for line in diff.lines() {
+        // Rename metadata appears in the header, before the first hunk
+        if current_hunk.is_none() {
+            if let Some(from) = line.strip_prefix("rename from ") {
+                renamed_from = Some(from);
+                continue;
+            }
+            if let Some(to) = line.strip_prefix("rename to ") {
+                renamed_to = Some(to);
+                continue;
+            }
+        }
+
         if line.starts_with("@@") {
             // Save previous hunk if exists
             if let Some(hunk) = current_hunk.take() {
@@ -299,10 +2720,13 @@ fn parse_diff_with_highlights(
             }
 
             // Parse hunk header: @@ -start,count +start,count @@
-            if let Some((old_start, old_lines, new_start, new_lines)) = parse_hunk_header(line) {
+            if let Some((old_start, old_lines, new_start, new_lines, context_name)) =
+                parse_hunk_header(line)
+            {
                 old_line_num = old_start;
                 new_line_num = new_start;
 
+                let context_language_hint = context_name.as_ref().map(|_| language.to_string());
                 current_hunk = Some(Hunk {
                     header: line.to_string(),
                     old_start,
@@ -310,6 +2734,8 @@ fn parse_diff_with_highlights(
                     new_start,
                     new_lines,
                     lines: Vec::new(),
+                    context_name,
+                    context_language_hint,
                 });
             }
         } else if let Some(ref mut hunk) = current_hunk {
@@ -379,18 +2805,29 @@ fn parse_diff_with_highlights(
         hunks.push(hunk);
     }
 
-    apply_word_level_highlights(&mut hunks);
+    apply_word_level_highlights(&mut hunks, word_regex);
 
-    (
-        hunks,
-        DiffStats {
-            additions: total_additions,
-            deletions: total_deletions,
-        },
-    )
+    let stats = DiffStats {
+        additions: total_additions,
+        deletions: total_deletions,
+    };
+    let summary = match (renamed_from, renamed_to) {
+        (Some(from), Some(to)) if hunks.is_empty() => {
+            format!("File renamed from {} to {}", from, to)
+        }
+        (Some(from), Some(to)) => format!(
+            "File renamed from {} to {}, {}",
+            from,
+            to,
+            text_summary(&stats, hunks.len())
+        ),
+        _ => text_summary(&stats, hunks.len()),
+    };
+
+    (hunks, stats, summary)
 }
 
-fn apply_word_level_highlights(hunks: &mut [Hunk]) {
+fn apply_word_level_highlights(hunks: &mut [Hunk], word_regex: Option<&Regex>) {
     for hunk in hunks.iter_mut() {
         let mut i = 0usize;
 
@@ -430,8 +2867,11 @@ fn apply_word_level_highlights(hunks: &mut [Hunk]) {
                 let deleted_line = &mut left[deleted_idx];
                 let added_line = &mut right[0];
 
-                let (deleted_ranges, added_ranges) =
-                    compute_word_change_ranges(&deleted_line.content, &added_line.content);
+                let (deleted_ranges, added_ranges) = compute_word_change_ranges(
+                    &deleted_line.content,
+                    &added_line.content,
+                    word_regex,
+                );
 
                 if !deleted_ranges.is_empty() {
                     deleted_line.highlights = merge_word_highlights(
@@ -458,10 +2898,21 @@ fn apply_word_level_highlights(hunks: &mut [Hunk]) {
 fn compute_word_change_ranges(
     old_line: &str,
     new_line: &str,
+    word_regex: Option<&Regex>,
 ) -> (Vec<(usize, usize)>, Vec<(usize, usize)>) {
+    let (old_tokens, new_tokens) = match word_regex {
+        Some(regex) => (
+            tokenize_with_regex(old_line, regex),
+            tokenize_with_regex(new_line, regex),
+        ),
+        None => (
+            tokenize_on_whitespace(old_line),
+            tokenize_on_whitespace(new_line),
+        ),
+    };
     let diff = TextDiff::configure()
         .algorithm(Algorithm::Myers)
-        .diff_words(old_line, new_line);
+        .diff_slices(&old_tokens, &new_tokens);
 
     let mut old_ranges = Vec::new();
     let mut new_ranges = Vec::new();
@@ -493,6 +2944,47 @@ fn compute_word_change_ranges(
     (merge_ranges(old_ranges), merge_ranges(new_ranges))
 }
 
+/// Split a line into alternating runs of whitespace and non-whitespace
+fn tokenize_on_whitespace(line: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0usize;
+    let mut in_whitespace = None;
+
+    for (idx, ch) in line.char_indices() {
+        let is_whitespace = ch.is_whitespace();
+        if in_whitespace.is_some_and(|prev| prev != is_whitespace) {
+            tokens.push(&line[start..idx]);
+            start = idx;
+        }
+        in_whitespace = Some(is_whitespace);
+    }
+    if start < line.len() {
+        tokens.push(&line[start..]);
+    }
+
+    tokens
+}
+
+/// Split a line into `regex` matches, keeping the text between matches as its own tokens
+/// so the token lengths still add up to the line length
+fn tokenize_with_regex<'a>(line: &'a str, regex: &Regex) -> Vec<&'a str> {
+    let mut tokens = Vec::new();
+    let mut last = 0usize;
+
+    for m in regex.find_iter(line).filter(|m| !m.as_str().is_empty()) {
+        if m.start() > last {
+            tokens.push(&line[last..m.start()]);
+        }
+        tokens.push(m.as_str());
+        last = m.end();
+    }
+    if last < line.len() {
+        tokens.push(&line[last..]);
+    }
+
+    tokens
+}
+
 fn merge_ranges(mut ranges: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
     if ranges.is_empty() {
         return ranges;
@@ -594,7 +3086,7 @@ fn merge_word_highlights(
     merged
 }
 
-fn parse_hunk_header(line: &str) -> Option<(u32, u32, u32, u32)> {
+fn parse_hunk_header(line: &str) -> Option<(u32, u32, u32, u32, Option<String>)> {
     // Format: @@ -old_start,old_count +new_start,new_count @@ optional context
     // Simple manual parsing since we don't have regex crate
     let parts: Vec<&str> = line.split(' ').collect();
@@ -608,7 +3100,15 @@ fn parse_hunk_header(line: &str) -> Option<(u32, u32, u32, u32)> {
     let (old_start, old_lines) = parse_range(old_part)?;
     let (new_start, new_lines) = parse_range(new_part)?;
 
-    Some((old_start, old_lines, new_start, new_lines))
+    // Context follows the second `@@`, e.g. `@@ -10,7 +10,7 @@ fn my_function() {`
+    let context = line
+        .splitn(3, "@@")
+        .nth(2)
+        .map(str::trim)
+        .filter(|context| !context.is_empty())
+        .map(str::to_string);
+
+    Some((old_start, old_lines, new_start, new_lines, context))
 }
 
 fn parse_range(range: &str) -> Option<(u32, u32)> {
@@ -620,7 +3120,7 @@ fn parse_range(range: &str) -> Option<(u32, u32)> {
 }
 
 /// Generate a synthetic diff for a new file (all lines as additions)
-fn generate_new_file_diff(content: &str, language: &str) -> (Vec<Hunk>, DiffStats) {
+fn generate_new_file_diff(content: &str, language: &str) -> (Vec<Hunk>, DiffStats, String) {
     let lines: Vec<&str> = content.lines().collect();
     let line_count = lines.len() as u32;
 
@@ -631,6 +3131,7 @@ fn generate_new_file_diff(content: &str, language: &str) -> (Vec<Hunk>, DiffStat
                 additions: 0,
                 deletions: 0,
             },
+            "New empty file".to_string(),
         );
     }
 
@@ -660,6 +3161,8 @@ fn generate_new_file_diff(content: &str, language: &str) -> (Vec<Hunk>, DiffStat
         new_start: 1,
         new_lines: line_count,
         lines: diff_lines,
+        context_name: None,
+        context_language_hint: None,
     };
 
     (
@@ -668,11 +3171,12 @@ fn generate_new_file_diff(content: &str, language: &str) -> (Vec<Hunk>, DiffStat
             additions: line_count,
             deletions: 0,
         },
+        format!("New file, +{} {}", line_count, plural_lines(line_count)),
     )
 }
 
 /// Generate a synthetic diff for a deleted file (all lines as deletions)
-fn generate_deleted_file_diff(content: &str, language: &str) -> (Vec<Hunk>, DiffStats) {
+fn generate_deleted_file_diff(content: &str, language: &str) -> (Vec<Hunk>, DiffStats, String) {
     let lines: Vec<&str> = content.lines().collect();
     let line_count = lines.len() as u32;
 
@@ -683,6 +3187,7 @@ fn generate_deleted_file_diff(content: &str, language: &str) -> (Vec<Hunk>, Diff
                 additions: 0,
                 deletions: 0,
             },
+            "Deleted empty file".to_string(),
         );
     }
 
@@ -712,6 +3217,8 @@ fn generate_deleted_file_diff(content: &str, language: &str) -> (Vec<Hunk>, Diff
         new_start: 0,
         new_lines: 0,
         lines: diff_lines,
+        context_name: None,
+        context_language_hint: None,
     };
 
     (
@@ -720,5 +3227,160 @@ fn generate_deleted_file_diff(content: &str, language: &str) -> (Vec<Hunk>, Diff
             additions: 0,
             deletions: line_count,
         },
+        format!(
+            "Deleted file, \u{2212}{} {}",
+            line_count,
+            plural_lines(line_count)
+        ),
     )
 }
+
+fn plural_lines(count: u32) -> &'static str {
+    if count == 1 {
+        "line"
+    } else {
+        "lines"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_diff_ignores_headers_and_positions() {
+        let first = concat!(
+            "diff --git a/src/lib.rs b/src/lib.rs\n",
+            "index 3b18e51..a5c1966 100644\n",
+            "--- a/src/lib.rs\n",
+            "+++ b/src/lib.rs\n",
+            "@@ -10,3 +10,3 @@ fn main() {\n",
+            " let a = 1;\n",
+            "-let b = 2;\n",
+            "+let b = 3;\n",
+            " let c = 4;\n",
+        );
+        let moved = concat!(
+            "diff --git a/src/lib.rs b/src/lib.rs\n",
+            "index 91d2c0f..7e4a2b8 100644\n",
+            "--- a/src/lib.rs\n",
+            "+++ b/src/lib.rs\n",
+            "@@ -42,3 +42,3 @@ impl Foo {\n",
+            " let a = 1;\n",
+            "-let b = 2;\n",
+            "+let b = 3;\n",
+            " let c = 4;\n",
+        );
+
+        assert_eq!(
+            canonical_diff(first),
+            "@@\n let a = 1;\n-let b = 2;\n+let b = 3;\n let c = 4;\n"
+        );
+        assert_eq!(canonical_diff(first), canonical_diff(moved));
+    }
+
+    #[test]
+    fn canonical_diff_keeps_no_newline_marker() {
+        let diff = concat!(
+            "diff --git a/a.txt b/a.txt\n",
+            "--- a/a.txt\n",
+            "+++ b/a.txt\n",
+            "@@ -1 +1 @@\n",
+            "-one\n",
+            "+two\n",
+            "\\ No newline at end of file\n",
+        );
+
+        assert_eq!(
+            canonical_diff(diff),
+            "@@\n-one\n+two\n\\ No newline at end of file\n"
+        );
+    }
+
+    #[test]
+    fn canonical_diff_of_empty_diff_is_empty() {
+        assert_eq!(canonical_diff(""), "");
+    }
+
+    #[test]
+    fn added_file_canonical_diff_matches_git_new_file_diff() {
+        let diff = concat!(
+            "diff --git a/a.txt b/a.txt\n",
+            "new file mode 100644\n",
+            "index 0000000..9ed40b4\n",
+            "--- /dev/null\n",
+            "+++ b/a.txt\n",
+            "@@ -0,0 +1,2 @@\n",
+            "+one\n",
+            "+two\n",
+            "\\ No newline at end of file\n",
+        );
+
+        assert_eq!(added_file_canonical_diff("one\ntwo"), canonical_diff(diff));
+        assert_eq!(added_file_canonical_diff("one\ntwo\n"), "@@\n+one\n+two\n");
+        assert_eq!(added_file_canonical_diff(""), "");
+    }
+
+    #[test]
+    fn word_change_ranges_split_on_whitespace() {
+        assert_eq!(
+            compute_word_change_ranges("let x = 1;", "let x = 2;", None),
+            (vec![(8, 10)], vec![(8, 10)])
+        );
+        // Without a word regex punctuation stays attached to the word
+        assert_eq!(
+            compute_word_change_ranges("call(foo, bar)", "call(foo, baz)", None),
+            (vec![(10, 14)], vec![(10, 14)])
+        );
+    }
+
+    #[test]
+    fn word_change_ranges_use_word_regex() {
+        let regex = Regex::new(r"[A-Za-z_]+|[0-9]+|[^[:space:]]").unwrap();
+        assert_eq!(
+            compute_word_change_ranges("call(foo, bar)", "call(foo, baz)", Some(&regex)),
+            (vec![(10, 13)], vec![(10, 13)])
+        );
+        assert_eq!(
+            compute_word_change_ranges("x=1", "x=10", Some(&regex)),
+            (vec![(2, 3)], vec![(2, 4)])
+        );
+    }
+
+    #[test]
+    fn word_change_ranges_are_byte_offsets_for_multibyte_text() {
+        assert_eq!(
+            compute_word_change_ranges("naïve café", "naïve cafés", None),
+            (vec![(7, 12)], vec![(7, 13)])
+        );
+        let regex = Regex::new(r"\w+|[^\s]").unwrap();
+        assert_eq!(
+            compute_word_change_ranges("「日本」語", "「日本」話", Some(&regex)),
+            (vec![(12, 15)], vec![(12, 15)])
+        );
+    }
+
+    #[test]
+    fn word_change_ranges_handle_empty_lines() {
+        assert_eq!(
+            compute_word_change_ranges("", "", None),
+            (Vec::new(), Vec::new())
+        );
+        assert_eq!(
+            compute_word_change_ranges("", "added text", None),
+            (Vec::new(), vec![(0, 10)])
+        );
+        assert_eq!(
+            compute_word_change_ranges("removed", "", None),
+            (vec![(0, 7)], Vec::new())
+        );
+    }
+
+    #[test]
+    fn diff_range_args_handles_pseudo_refs() {
+        assert_eq!(diff_range_args("abc", "def"), vec!["abc...def"]);
+        assert_eq!(diff_range_args("abc", "WORKING_TREE"), vec!["abc"]);
+        assert!(diff_range_args("INDEX", "WORKING_TREE").is_empty());
+        assert_eq!(diff_range_args("abc", "INDEX"), vec!["--cached", "abc"]);
+    }
+}
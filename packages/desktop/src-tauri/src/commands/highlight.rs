@@ -1,10 +1,14 @@
+use libloading::Library;
 use once_cell::sync::Lazy;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::Path;
-use std::sync::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use tree_sitter::Language;
 use tree_sitter_highlight::{HighlightConfiguration, HighlightEvent, Highlighter};
+use tree_sitter_language::LanguageFn;
 
 /// Highlight span returned to the frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +18,30 @@ pub struct HighlightSpan {
     pub scope: String,
 }
 
+/// One file in a `highlight_multiple_files` batch
+#[derive(Debug, Clone, Deserialize)]
+pub struct HighlightRequest {
+    pub id: String,
+    pub content: String,
+    pub language: String,
+}
+
+/// Result for one `HighlightRequest`; `error` is set if that file failed
+#[derive(Debug, Clone, Serialize)]
+pub struct HighlightResponse {
+    pub id: String,
+    pub spans: Vec<HighlightSpan>,
+    pub error: Option<String>,
+}
+
+/// A highlight scope and the built-in languages whose grammars produce it
+#[derive(Debug, Clone, Serialize)]
+pub struct ScopeInfo {
+    pub scope: String,
+    #[serde(rename = "exampleLanguages")]
+    pub example_languages: Vec<String>,
+}
+
 /// Language info for highlighting
 #[derive(Debug, Clone)]
 pub struct LanguageInfo {
@@ -71,6 +99,30 @@ const LANGUAGES: &[LanguageInfo] = &[
         name: "bash",
         extensions: &["sh", "bash", "zsh"],
     },
+    LanguageInfo {
+        name: "zig",
+        extensions: &["zig", "zon"],
+    },
+    LanguageInfo {
+        name: "elixir",
+        extensions: &["ex", "exs"],
+    },
+    LanguageInfo {
+        name: "heex",
+        extensions: &["heex"],
+    },
+    LanguageInfo {
+        name: "haskell",
+        extensions: &["hs", "lhs"],
+    },
+    LanguageInfo {
+        name: "swift",
+        extensions: &["swift"],
+    },
+    LanguageInfo {
+        name: "env",
+        extensions: &["env"],
+    },
 ];
 
 /// Standard highlight names that Tree-sitter uses
@@ -106,12 +158,23 @@ const HIGHLIGHT_NAMES: &[&str] = &[
     "variable.parameter",
 ];
 
+/// Scopes emitted by the hand-written dotenv highlighter (`highlight_env`)
+const ENV_SCOPES: &[&str] = &["comment", "keyword", "variable", "operator", "string"];
+
 /// Cached highlight configurations per language
-/// Since HighlightConfiguration doesn't implement Clone, we store them in a HashMap
-/// and return references or create new ones as needed
-static CONFIGS: Lazy<Mutex<HashMap<String, HighlightConfiguration>>> =
+/// Since HighlightConfiguration doesn't implement Clone, they are shared via Arc so
+/// highlighting can run without holding the lock
+static CONFIGS: Lazy<Mutex<HashMap<String, Arc<HighlightConfiguration>>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
 
+/// Languages whose highlight configuration failed to build (e.g. a grammar/query
+/// mismatch). These are treated as plain text instead of being retried on every call.
+static FAILED_CONFIGS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Shared libraries backing externally loaded grammars.
+/// Never unloaded: the `Language` pointers in CONFIGS point into these libraries.
+static EXTERNAL_LIBRARIES: Lazy<Mutex<Vec<Library>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
 /// Detect language from file path extension
 #[tauri::command]
 pub fn detect_language(file_path: String) -> String {
@@ -140,10 +203,116 @@ pub fn detect_language_from_path(file_path: &str) -> String {
         "dockerfile" => "bash".to_string(),
         "makefile" => "bash".to_string(),
         ".bashrc" | ".zshrc" | ".bash_profile" => "bash".to_string(),
+        "build.zig" | "build.zig.zon" => "zig".to_string(),
+        "mix.exs" => "elixir".to_string(),
+        "cabal.project" => "haskell".to_string(),
+        "stack.yaml" => "yaml".to_string(),
+        name if name.ends_with(".cabal") => "haskell".to_string(),
+        name if name == ".env" || name.starts_with(".env.") => "env".to_string(),
         _ => "text".to_string(),
     }
 }
 
+/// How much of a file `detect_language_from_content` looks at
+const CONTENT_SNIFF_BYTES: usize = 4096;
+
+/// Detect language using file content as a tiebreaker for ambiguous
+/// extensions (`.h`: C/C++/Objective-C, `.m`: Objective-C/MATLAB) and a
+/// shebang fallback for files the path alone can't identify.
+/// `c`, `cpp`, `objc` and `matlab` have no bundled grammar; use
+/// `detect_highlight_language` when the result feeds the highlighter.
+#[tauri::command]
+pub fn detect_language_advanced(file_path: String, content_bytes: Vec<u8>) -> String {
+    detect_language_from_content(&file_path, &content_bytes)
+}
+
+pub fn detect_language_from_content(file_path: &str, first_bytes: &[u8]) -> String {
+    let sniff = &first_bytes[..first_bytes.len().min(CONTENT_SNIFF_BYTES)];
+    let content = String::from_utf8_lossy(sniff);
+
+    let extension = Path::new(file_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    let is_objc = ["@interface", "@implementation", "@protocol", "#import"]
+        .iter()
+        .any(|marker| content.contains(marker));
+
+    match extension.as_str() {
+        "h" => {
+            let is_cpp = [
+                "#include <iostream>",
+                "namespace ",
+                "template <",
+                "template<",
+                "std::",
+                "class ",
+                "public:",
+            ]
+            .iter()
+            .any(|marker| content.contains(marker));
+            if is_objc {
+                "objc".to_string()
+            } else if is_cpp {
+                "cpp".to_string()
+            } else {
+                "c".to_string()
+            }
+        }
+        "m" => {
+            let is_matlab = content.lines().map(str::trim_start).any(|line| {
+                line.starts_with("function ") || line.starts_with('%') || line == "end"
+            });
+            if is_matlab && !is_objc {
+                "matlab".to_string()
+            } else {
+                "objc".to_string()
+            }
+        }
+        _ => match detect_language_from_path(file_path).as_str() {
+            "text" => detect_language_from_shebang(&content).unwrap_or_else(|| "text".to_string()),
+            language => language.to_string(),
+        },
+    }
+}
+
+/// Language to highlight a file with, using its content when available.
+/// Content-detected languages without a bundled grammar fall back to `text`.
+pub fn detect_highlight_language(file_path: &str, content: Option<&str>) -> String {
+    let Some(content) = content else {
+        return detect_language_from_path(file_path);
+    };
+
+    let language = detect_language_from_content(file_path, content.as_bytes());
+    if LANGUAGES.iter().any(|lang| lang.name == language) {
+        language
+    } else {
+        "text".to_string()
+    }
+}
+
+/// Map a `#!` interpreter line to a language, e.g. `#!/usr/bin/env python3`
+fn detect_language_from_shebang(content: &str) -> Option<String> {
+    let shebang = content.lines().next()?.strip_prefix("#!")?;
+    let mut parts = shebang.split_whitespace();
+    let mut interpreter = parts.next()?.rsplit('/').next()?;
+    if interpreter == "env" {
+        interpreter = parts.find(|p| !p.starts_with('-'))?;
+    }
+
+    let language = match interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.') {
+        "sh" | "bash" | "zsh" => "bash",
+        "python" => "python",
+        "node" | "deno" | "bun" => "javascript",
+        "elixir" => "elixir",
+        "runhaskell" | "runghc" => "haskell",
+        _ => return None,
+    };
+    Some(language.to_string())
+}
+
 /// Get language and query info for a language name
 fn get_language_info(
     language: &str,
@@ -218,10 +387,50 @@ fn get_language_info(
             "",
             "",
         )),
+        "zig" => Some((
+            tree_sitter_zig::LANGUAGE.into(),
+            tree_sitter_zig::HIGHLIGHTS_QUERY,
+            "",
+            "",
+        )),
+        "elixir" => Some((
+            tree_sitter_elixir::LANGUAGE.into(),
+            tree_sitter_elixir::HIGHLIGHTS_QUERY,
+            tree_sitter_elixir::INJECTIONS_QUERY,
+            "",
+        )),
+        "heex" => Some((
+            tree_sitter_heex::LANGUAGE.into(),
+            tree_sitter_heex::HIGHLIGHTS_QUERY,
+            tree_sitter_heex::INJECTIONS_QUERY,
+            "",
+        )),
+        "haskell" => Some((
+            tree_sitter_haskell::LANGUAGE.into(),
+            tree_sitter_haskell::HIGHLIGHTS_QUERY,
+            tree_sitter_haskell::INJECTIONS_QUERY,
+            tree_sitter_haskell::LOCALS_QUERY,
+        )),
+        // Pinned in Cargo.toml to a release generated for the bundled tree-sitter's ABI
+        "swift" => Some((
+            tree_sitter_swift::LANGUAGE.into(),
+            tree_sitter_swift::HIGHLIGHTS_QUERY,
+            "",
+            tree_sitter_swift::LOCALS_QUERY,
+        )),
         _ => None,
     }
 }
 
+/// Languages embedded in another language via its injections query.
+/// Their configs are loaded up front so injected regions can be highlighted.
+fn injected_languages(language: &str) -> &'static [&'static str] {
+    match language {
+        "heex" => &["elixir"],
+        _ => &[],
+    }
+}
+
 /// Ensure a highlight configuration exists for the given language
 fn ensure_config(language: &str) -> bool {
     let mut configs = CONFIGS.lock().unwrap_or_else(|e| e.into_inner());
@@ -230,19 +439,216 @@ fn ensure_config(language: &str) -> bool {
         return true;
     }
 
+    let mut failed = FAILED_CONFIGS.lock().unwrap_or_else(|e| e.into_inner());
+    if failed.contains(language) {
+        return false;
+    }
+
     if let Some((lang, highlights, injections, locals)) = get_language_info(language) {
-        if let Ok(mut config) =
-            HighlightConfiguration::new(lang, language, highlights, injections, locals)
-        {
-            config.configure(HIGHLIGHT_NAMES);
-            configs.insert(language.to_string(), config);
-            return true;
+        match HighlightConfiguration::new(lang, language, highlights, injections, locals) {
+            Ok(mut config) => {
+                config.configure(HIGHLIGHT_NAMES);
+                configs.insert(language.to_string(), Arc::new(config));
+                return true;
+            }
+            Err(_) => {
+                // A broken grammar falls back to plain text rather than failing the pipeline
+                failed.insert(language.to_string());
+            }
         }
     }
 
     false
 }
 
+/// Directory holding user-provided grammars: `.revi/grammars/<language>/`
+fn grammars_dir(repo_root: &str) -> PathBuf {
+    Path::new(repo_root).join(".revi").join("grammars")
+}
+
+/// External grammar names become a directory under `grammars_dir` and a `CONFIGS`
+/// key, so they must be a single `[A-Za-z0-9_-]+` component and must not shadow a
+/// built-in language (or the `text` fallback).
+fn validate_grammar_name(language_name: &str) -> Result<(), String> {
+    let is_component = !language_name.is_empty()
+        && language_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !is_component {
+        return Err(format!("Invalid grammar name: '{}'", language_name));
+    }
+
+    if language_name == "text" || LANGUAGES.iter().any(|lang| lang.name == language_name) {
+        return Err(format!(
+            "Grammar name '{}' is already a built-in language",
+            language_name
+        ));
+    }
+
+    Ok(())
+}
+
+/// Load an external tree-sitter grammar and register it for highlighting.
+///
+/// Expected layout for a language `protobuf`:
+///   - `.revi/grammars/protobuf/<grammar_path>` — shared library (`.so` / `.dylib` / `.dll`)
+///     exporting `tree_sitter_protobuf` (dashes in the language name become underscores)
+///   - `.revi/grammars/protobuf/highlights.scm` — required highlights query
+///   - `.revi/grammars/protobuf/injections.scm`, `locals.scm` — optional
+///
+/// Once loaded, `highlight_code` accepts `language_name` like any built-in language.
+#[tauri::command]
+pub fn load_external_grammar(
+    repo_root: String,
+    language_name: String,
+    grammar_path: String,
+) -> Result<(), String> {
+    validate_grammar_name(&language_name)?;
+
+    let language_dir = grammars_dir(&repo_root).join(&language_name);
+    let root = language_dir
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve grammar directory: {}", e))?;
+    let library_path = root
+        .join(&grammar_path)
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve grammar library: {}", e))?;
+    if !library_path.starts_with(&root) {
+        return Err("Grammar library escapes the grammar directory".to_string());
+    }
+
+    let highlights = fs::read_to_string(root.join("highlights.scm"))
+        .map_err(|e| format!("Failed to read highlights.scm: {}", e))?;
+    let injections = fs::read_to_string(root.join("injections.scm")).unwrap_or_default();
+    let locals = fs::read_to_string(root.join("locals.scm")).unwrap_or_default();
+
+    let symbol_name = format!("tree_sitter_{}", language_name.replace('-', "_"));
+
+    // SAFETY: the library is a tree-sitter grammar supplied by the user, and the
+    // symbol has the standard `const TSLanguage *tree_sitter_<name>(void)` signature.
+    // The library is kept alive in EXTERNAL_LIBRARIES for the rest of the process.
+    let (library, language) = unsafe {
+        let library = Library::new(&library_path)
+            .map_err(|e| format!("Failed to load grammar library: {}", e))?;
+        let language_fn = *library
+            .get::<unsafe extern "C" fn() -> *const ()>(symbol_name.as_bytes())
+            .map_err(|e| format!("Symbol '{}' not found: {}", symbol_name, e))?;
+        let language = Language::new(LanguageFn::from_raw(language_fn));
+        (library, language)
+    };
+
+    let mut config =
+        HighlightConfiguration::new(language, &language_name, &highlights, &injections, &locals)
+            .map_err(|e| format!("Invalid grammar queries: {:?}", e))?;
+    config.configure(HIGHLIGHT_NAMES);
+
+    EXTERNAL_LIBRARIES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(library);
+    FAILED_CONFIGS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&language_name);
+    CONFIGS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(language_name, Arc::new(config));
+
+    Ok(())
+}
+
+/// List language names that have a directory under `.revi/grammars/`
+#[tauri::command]
+pub fn list_external_grammars(repo_root: String) -> Result<Vec<String>, String> {
+    let dir = grammars_dir(&repo_root);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(&dir).map_err(|e| format!("Failed to read grammars dir: {}", e))?;
+
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str().map(|n| n.to_string()))
+        .collect();
+    names.sort();
+
+    Ok(names)
+}
+
+/// List every highlight scope with the built-in languages that can produce it,
+/// for theme authors. Scopes no grammar captures have no languages.
+#[tauri::command]
+pub fn get_highlight_scope_legend() -> Vec<ScopeInfo> {
+    let mut languages_by_scope: HashMap<&str, Vec<String>> = HashMap::new();
+    for lang in LANGUAGES {
+        let scopes: HashSet<&str> = if lang.name == "env" {
+            ENV_SCOPES.iter().copied().collect()
+        } else if let Some((_, highlights, _, _)) = get_language_info(lang.name) {
+            query_scopes(highlights)
+        } else {
+            continue;
+        };
+
+        for scope in scopes {
+            languages_by_scope
+                .entry(scope)
+                .or_default()
+                .push(lang.name.to_string());
+        }
+    }
+
+    HIGHLIGHT_NAMES
+        .iter()
+        .map(|scope| ScopeInfo {
+            scope: scope.to_string(),
+            example_languages: languages_by_scope.remove(scope).unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Highlight names a highlights query's captures resolve to
+fn query_scopes(query: &str) -> HashSet<&'static str> {
+    query
+        .split('@')
+        .skip(1)
+        .filter_map(|rest| {
+            let capture = rest
+                .split(|c: char| !(c.is_alphanumeric() || matches!(c, '.' | '_' | '-')))
+                .next()?;
+            resolve_capture(capture)
+        })
+        .collect()
+}
+
+/// Map a capture name to a `HIGHLIGHT_NAMES` entry the way tree-sitter-highlight
+/// does: the name with the most parts, all of which appear in the capture
+/// (`function.method.call` -> "function")
+fn resolve_capture(capture: &str) -> Option<&'static str> {
+    let capture_parts: Vec<&str> = capture.split('.').collect();
+    HIGHLIGHT_NAMES
+        .iter()
+        .filter(|name| name.split('.').all(|part| capture_parts.contains(&part)))
+        .max_by_key(|name| name.split('.').count())
+        .copied()
+}
+
+/// List the distinct scopes the highlighter produces for some content, sorted
+#[tauri::command]
+pub fn get_scopes_used_in_file(content: String, language: String) -> Result<Vec<String>, String> {
+    let spans = highlight_code_internal(&content, &language)?;
+    let mut scopes: Vec<String> = spans
+        .into_iter()
+        .map(|span| span.scope)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    scopes.sort();
+    Ok(scopes)
+}
+
 /// Highlight code and return spans
 #[tauri::command]
 pub fn highlight_code(content: String, language: String) -> Result<Vec<HighlightSpan>, String> {
@@ -254,22 +660,45 @@ pub fn highlight_code_internal(
     content: &str,
     language: &str,
 ) -> Result<Vec<HighlightSpan>, String> {
+    highlight_code_unmerged(content, language).map(|spans| merge_adjacent_spans(content, spans))
+}
+
+/// Raw spans straight from the highlighter, one per source event
+fn highlight_code_unmerged(content: &str, language: &str) -> Result<Vec<HighlightSpan>, String> {
+    // No stable tree-sitter grammar for dotenv; it's simple enough to scan by hand
+    if language == "env" {
+        return Ok(highlight_env(content));
+    }
+
     // Ensure config exists
     if !ensure_config(language) {
         return Ok(Vec::new()); // Return empty for unsupported languages
     }
+    for injected in injected_languages(language) {
+        ensure_config(injected);
+    }
 
-    let configs = CONFIGS.lock().unwrap_or_else(|e| e.into_inner());
-    let config = match configs.get(language) {
-        Some(c) => c,
-        None => return Ok(Vec::new()),
+    // Snapshot the needed configs so the lock isn't held while highlighting
+    let (config, injected) = {
+        let configs = CONFIGS.lock().unwrap_or_else(|e| e.into_inner());
+        let config = match configs.get(language) {
+            Some(c) => Arc::clone(c),
+            None => return Ok(Vec::new()),
+        };
+        let injected: HashMap<&str, Arc<HighlightConfiguration>> = injected_languages(language)
+            .iter()
+            .filter_map(|name| configs.get(*name).map(|c| (*name, Arc::clone(c))))
+            .collect();
+        (config, injected)
     };
 
     let mut highlighter = Highlighter::new();
     let source = content.as_bytes();
 
     let highlights = highlighter
-        .highlight(config, source, None, |_| None)
+        .highlight(&config, source, None, |name| {
+            injected.get(name).map(|c| c.as_ref())
+        })
         .map_err(|e| format!("Highlight error: {:?}", e))?;
 
     let mut spans: Vec<HighlightSpan> = Vec::new();
@@ -300,6 +729,143 @@ pub fn highlight_code_internal(
     Ok(spans)
 }
 
+/// Highlight dotenv `KEY=VALUE` lines: key as variable, `=` as operator,
+/// value as string, `#` lines as comments, and a leading `export` as keyword
+fn highlight_env(content: &str) -> Vec<HighlightSpan> {
+    let span = |start: usize, end: usize, scope: &str| HighlightSpan {
+        start: start as u32,
+        end: end as u32,
+        scope: scope.to_string(),
+    };
+    let mut spans = Vec::new();
+    let mut offset = 0;
+
+    for line in content.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+
+        let text = line.trim_end_matches(['\n', '\r']);
+        let indent = text.len() - text.trim_start().len();
+        let mut pos = line_start + indent;
+        let mut rest = text.trim_start();
+
+        if rest.is_empty() {
+            continue;
+        }
+        if rest.starts_with('#') {
+            spans.push(span(pos, pos + rest.len(), "comment"));
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix("export ") {
+            spans.push(span(pos, pos + "export".len(), "keyword"));
+            let skipped = rest.len() - after.trim_start().len();
+            pos += skipped;
+            rest = after.trim_start();
+        }
+
+        let Some(eq) = rest.find('=') else {
+            continue;
+        };
+        let key_len = rest[..eq].trim_end().len();
+        if key_len > 0 {
+            spans.push(span(pos, pos + key_len, "variable"));
+        }
+        spans.push(span(pos + eq, pos + eq + 1, "operator"));
+        let value = &rest[eq + 1..];
+        let value_trimmed = value.trim_start();
+        if !value_trimmed.is_empty() {
+            let value_start = pos + eq + 1 + (value.len() - value_trimmed.len());
+            spans.push(span(
+                value_start,
+                value_start + value_trimmed.trim_end().len(),
+                "string",
+            ));
+        }
+    }
+
+    spans
+}
+
+/// Collapse consecutive spans that touch and share a scope into one span.
+/// Nested captures (e.g. doc comments inside comments) otherwise produce many
+/// tiny fragments, which bloats the IPC payload. Spans are never merged across
+/// a newline, since per-line consumers clamp each span to its starting line.
+fn merge_adjacent_spans(content: &str, spans: Vec<HighlightSpan>) -> Vec<HighlightSpan> {
+    let mut merged: Vec<HighlightSpan> = Vec::with_capacity(spans.len());
+    let source = content.as_bytes();
+
+    for span in spans {
+        if let Some(last) = merged.last_mut() {
+            let crosses_line = source
+                .get(last.start as usize..span.end as usize)
+                .is_none_or(|text| text.contains(&b'\n'));
+            if span.start == last.end && span.scope == last.scope && !crosses_line {
+                last.end = span.end;
+                continue;
+            }
+        }
+        merged.push(span);
+    }
+
+    merged
+}
+
+/// Highlight several files in one IPC round trip, in parallel.
+/// Each request is independent: a failure is reported in its `error` field
+/// without affecting the others.
+#[tauri::command]
+pub fn highlight_multiple_files(requests: Vec<HighlightRequest>) -> Vec<HighlightResponse> {
+    requests
+        .into_par_iter()
+        .map(
+            |request| match highlight_code_internal(&request.content, &request.language) {
+                Ok(spans) => HighlightResponse {
+                    id: request.id,
+                    spans,
+                    error: None,
+                },
+                Err(e) => HighlightResponse {
+                    id: request.id,
+                    spans: Vec::new(),
+                    error: Some(e),
+                },
+            },
+        )
+        .collect()
+}
+
+/// Convert span offsets from UTF-8 bytes into UTF-16 code units, the positions
+/// JavaScript strings index by. Offsets inside a multi-byte character snap to
+/// its start; offsets past the end clamp to the content length.
+#[tauri::command]
+pub fn normalize_spans_to_char_offsets(
+    content: String,
+    spans: Vec<HighlightSpan>,
+) -> Vec<HighlightSpan> {
+    normalize_spans(&content, spans)
+}
+
+pub fn normalize_spans(content: &str, spans: Vec<HighlightSpan>) -> Vec<HighlightSpan> {
+    // offsets[byte] = UTF-16 position of the character containing that byte
+    let mut offsets = Vec::with_capacity(content.len() + 1);
+    let mut position = 0u32;
+    for ch in content.chars() {
+        offsets.extend(std::iter::repeat_n(position, ch.len_utf8()));
+        position += ch.len_utf16() as u32;
+    }
+    offsets.push(position);
+
+    let convert = |byte: u32| offsets[(byte as usize).min(content.len())];
+    spans
+        .into_iter()
+        .map(|span| HighlightSpan {
+            start: convert(span.start),
+            end: convert(span.end),
+            scope: span.scope,
+        })
+        .collect()
+}
+
 /// Highlight a single line of code
 /// Returns spans with positions relative to the line start
 pub fn highlight_line(line: &str, language: &str) -> Vec<HighlightSpan> {
@@ -372,6 +938,20 @@ pub fn highlight_file_lines(content: &str, language: &str) -> Vec<Vec<HighlightS
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_grammar_name_validation() {
+        assert!(validate_grammar_name("protobuf").is_ok());
+        assert!(validate_grammar_name("my-lang_2").is_ok());
+        assert!(validate_grammar_name("").is_err());
+        assert!(validate_grammar_name("..").is_err());
+        assert!(validate_grammar_name("../../etc").is_err());
+        assert!(validate_grammar_name("a/b").is_err());
+        assert!(validate_grammar_name("a\\b").is_err());
+        assert!(validate_grammar_name("rust").is_err());
+        assert!(validate_grammar_name("env").is_err());
+        assert!(validate_grammar_name("text").is_err());
+    }
+
     #[test]
     fn test_language_detection() {
         assert_eq!(detect_language_from_path("src/main.ts"), "typescript");
@@ -388,6 +968,178 @@ mod tests {
         assert_eq!(detect_language_from_path("config.yaml"), "yaml");
         assert_eq!(detect_language_from_path("script.sh"), "bash");
         assert_eq!(detect_language_from_path("types.d.ts"), "typescript");
+        assert_eq!(detect_language_from_path("src/main.zig"), "zig");
+        assert_eq!(detect_language_from_path("build.zig"), "zig");
+        assert_eq!(detect_language_from_path("build.zig.zon"), "zig");
+        assert_eq!(detect_language_from_path("lib/my_app.ex"), "elixir");
+        assert_eq!(detect_language_from_path("test/my_test.exs"), "elixir");
+        assert_eq!(detect_language_from_path("mix.exs"), "elixir");
+        assert_eq!(detect_language_from_path("index.html.heex"), "heex");
+        assert_eq!(detect_language_from_path("src/Main.hs"), "haskell");
+        assert_eq!(detect_language_from_path("Lib.lhs"), "haskell");
+        assert_eq!(detect_language_from_path("package.cabal"), "haskell");
+        assert_eq!(detect_language_from_path("cabal.project"), "haskell");
+        assert_eq!(detect_language_from_path("stack.yaml"), "yaml");
+        assert_eq!(detect_language_from_path("App/AppDelegate.swift"), "swift");
+        assert_eq!(detect_language_from_path("Package.swift"), "swift");
+        assert_eq!(detect_language_from_path("ContentView.swift"), "swift");
+        assert_eq!(detect_language_from_path(".env"), "env");
+        assert_eq!(detect_language_from_path(".env.local"), "env");
+        assert_eq!(detect_language_from_path("config/.env.production"), "env");
+        assert_eq!(detect_language_from_path("docker/app.env"), "env");
         assert_eq!(detect_language_from_path("unknown.xyz"), "text");
     }
+
+    #[test]
+    fn test_content_language_detection() {
+        let cpp = b"#pragma once\n#include <iostream>\n\nclass Foo {};\n";
+        let objc = b"#import <Foundation/Foundation.h>\n@interface Foo : NSObject\n@end\n";
+        let c = b"#include <stdio.h>\n\nint add(int a, int b);\n";
+        let matlab = b"function y = square(x)\n  % square a number\n  y = x.^2;\nend\n";
+
+        assert_eq!(detect_language_from_content("foo.h", cpp), "cpp");
+        assert_eq!(detect_language_from_content("foo.h", objc), "objc");
+        assert_eq!(detect_language_from_content("foo.h", c), "c");
+        assert_eq!(detect_language_from_content("Foo.m", objc), "objc");
+        assert_eq!(detect_language_from_content("square.m", matlab), "matlab");
+        assert_eq!(
+            detect_language_from_content("bin/deploy", b"#!/usr/bin/env python3\nprint()\n"),
+            "python"
+        );
+        assert_eq!(
+            detect_language_from_content("bin/run", b"#!/bin/bash\necho hi\n"),
+            "bash"
+        );
+        assert_eq!(
+            detect_language_from_content("src/main.rs", b"fn main() {}"),
+            "rust"
+        );
+        assert_eq!(detect_language_from_content("notes", b"hello"), "text");
+
+        assert_eq!(
+            detect_highlight_language("bin/deploy", Some("#!/usr/bin/env python3\n")),
+            "python"
+        );
+        assert_eq!(
+            detect_highlight_language("foo.h", Some("#include <stdio.h>\n")),
+            "text"
+        );
+        assert_eq!(detect_highlight_language("src/main.rs", None), "rust");
+    }
+
+    #[test]
+    fn test_env_highlighting() {
+        let content = "# comment\nAPI_KEY=abc123\nexport PORT = 8080\nEMPTY=\n";
+        let spans = highlight_code_internal(content, "env").unwrap();
+        let tokens: Vec<(&str, &str)> = spans
+            .iter()
+            .map(|s| (&content[s.start as usize..s.end as usize], s.scope.as_str()))
+            .collect();
+
+        assert_eq!(
+            tokens,
+            vec![
+                ("# comment", "comment"),
+                ("API_KEY", "variable"),
+                ("=", "operator"),
+                ("abc123", "string"),
+                ("export", "keyword"),
+                ("PORT", "variable"),
+                ("=", "operator"),
+                ("8080", "string"),
+                ("EMPTY", "variable"),
+                ("=", "operator"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_swift_highlighting() {
+        let spans = highlight_code_internal("let x = 1", "swift").unwrap();
+        assert!(!spans.is_empty());
+        assert!(spans.iter().any(|s| s.scope == "keyword"));
+    }
+
+    #[test]
+    fn test_query_scopes() {
+        let query = r#"
+            (parameter (identifier) @variable.parameter)
+            (call_expression function: (identifier) @function.method.call)
+            ["+" "@"] @operator
+            (unknown) @spell
+        "#;
+        let scopes = query_scopes(query);
+        let mut scopes: Vec<&str> = scopes.into_iter().collect();
+        scopes.sort();
+        assert_eq!(scopes, vec!["function", "operator", "variable.parameter"]);
+
+        let legend = get_highlight_scope_legend();
+        assert_eq!(legend.len(), HIGHLIGHT_NAMES.len());
+        let comment = legend.iter().find(|info| info.scope == "comment").unwrap();
+        assert!(comment.example_languages.contains(&"env".to_string()));
+    }
+
+    #[test]
+    fn test_normalize_spans() {
+        let span = |start, end| HighlightSpan {
+            start,
+            end,
+            scope: "string".to_string(),
+        };
+
+        // "é" is 2 bytes / 1 unit, "😀" is 4 bytes / 2 units
+        let content = "é😀a";
+        let spans = normalize_spans(
+            content,
+            vec![span(0, 2), span(2, 6), span(6, 7), span(3, 99)],
+        );
+        let offsets: Vec<(u32, u32)> = spans.iter().map(|s| (s.start, s.end)).collect();
+        assert_eq!(offsets, vec![(0, 1), (1, 3), (3, 4), (1, 4)]);
+
+        let ascii = normalize_spans("let x", vec![span(0, 3)]);
+        assert_eq!((ascii[0].start, ascii[0].end), (0, 3));
+    }
+
+    #[test]
+    fn test_merge_adjacent_spans() {
+        let span = |start, end, scope: &str| HighlightSpan {
+            start,
+            end,
+            scope: scope.to_string(),
+        };
+        let spans = vec![
+            span(0, 3, "comment"),
+            span(3, 10, "comment"),
+            span(11, 13, "keyword"),
+            span(13, 14, "punctuation"),
+            span(14, 15, "punctuation"),
+        ];
+
+        let merged = merge_adjacent_spans("// comment\nfn();", spans);
+        assert_eq!(merged.len(), 3);
+        assert_eq!((merged[0].start, merged[0].end), (0, 10));
+        assert_eq!((merged[1].start, merged[1].end), (11, 13));
+        assert_eq!((merged[2].start, merged[2].end), (13, 15));
+
+        // Same-scope spans on either side of a newline stay separate
+        let merged = merge_adjacent_spans(
+            "// a\n// b",
+            vec![span(0, 5, "comment"), span(5, 9, "comment")],
+        );
+        assert_eq!(merged.len(), 2);
+
+        let source =
+            "/// Parses a config file.\n/// Returns the default on error.\npub fn load() {}\n";
+        let lines = highlight_file_lines(source, "rust");
+        for (idx, text) in source.lines().take(2).enumerate() {
+            let comment = lines[idx]
+                .iter()
+                .find(|s| s.scope == "comment")
+                .unwrap_or_else(|| panic!("line {} has no comment span", idx));
+            // Line comments own their trailing newline
+            assert_eq!(comment.start, 0);
+            assert!(comment.end as usize >= text.len());
+        }
+        assert!(lines[2].iter().any(|s| s.scope == "keyword"));
+    }
 }
@@ -1,14 +1,26 @@
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
 use nanoid::nanoid;
+use once_cell::sync::Lazy;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fs;
-use std::io::Read;
-use std::path::Path;
-use std::process::Command;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
 use tauri::{AppHandle, Manager};
 
+use super::highlight::detect_language_from_path;
+
+/// Lock files taken by each window, so they can be released when it closes
+static HELD_SESSION_LOCKS: Lazy<Mutex<HashMap<String, HashSet<PathBuf>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A lock whose holder hasn't saved or re-acquired it for this long is ignored
+const SESSION_LOCK_TTL_MINUTES: i64 = 30;
+
 /// Information about the last opened session, persisted to app data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LastSession {
@@ -55,6 +67,101 @@ pub struct ReviewManifest {
     pub created_at: String,
     #[serde(rename = "comparisonMode")]
     pub comparison_mode: Option<ComparisonMode>,
+    #[serde(rename = "prInfo", default, skip_serializing_if = "Option::is_none")]
+    pub pr_info: Option<PrInfo>,
+    /// Files omitted because they fall outside the sparse-checkout cone
+    #[serde(rename = "skippedCount", default)]
+    pub skipped_count: u32,
+    /// Free-form reviewer notes about the change as a whole
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub status: ReviewStatus,
+    /// User-facing session name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Session covers exactly one commit against its first parent
+    #[serde(rename = "isSingleCommit", default)]
+    pub is_single_commit: bool,
+}
+
+/// A session manifest with local paths stripped so it can be shared between machines.
+/// `repo_root` is replaced by the origin URL and the worktree by its branch name.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PortableSession {
+    pub version: u32,
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+    #[serde(rename = "originUrl")]
+    pub origin_url: Option<String>,
+    pub base: RefInfo,
+    pub head: RefInfo,
+    pub branch: Option<String>,
+    pub files: Vec<FileEntry>,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    #[serde(rename = "comparisonMode")]
+    pub comparison_mode: Option<ComparisonMode>,
+    #[serde(rename = "prInfo", default, skip_serializing_if = "Option::is_none")]
+    pub pr_info: Option<PrInfo>,
+    #[serde(rename = "skippedCount", default)]
+    pub skipped_count: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub status: ReviewStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(rename = "isSingleCommit", default)]
+    pub is_single_commit: bool,
+}
+
+/// A session's freshly computed base, flagged if it moved since the session was created
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionMergeBase {
+    #[serde(flatten)]
+    pub base: RefInfo,
+    #[serde(rename = "baseShaChanged")]
+    pub base_sha_changed: bool,
+}
+
+/// How far HEAD has moved since a session was created
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionAgeWarning {
+    #[serde(rename = "sessionAgeHours")]
+    pub session_age_hours: f64,
+    #[serde(rename = "commitsSinceSession")]
+    pub commits_since_session: u32,
+    #[serde(rename = "headShaChanged")]
+    pub head_sha_changed: bool,
+    /// "none" | "info" | "warn" | "stale"
+    #[serde(rename = "warningLevel")]
+    pub warning_level: String,
+}
+
+/// Overall outcome of a review session
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReviewStatus {
+    #[default]
+    Pending,
+    InProgress,
+    Approved,
+    ChangesRequested,
+}
+
+/// GitHub pull request a session was created from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrInfo {
+    pub title: String,
+    pub number: u64,
+    #[serde(rename = "htmlUrl")]
+    pub html_url: String,
+    pub author: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -79,6 +186,90 @@ pub struct FileEntry {
     #[serde(rename = "renamedFrom")]
     pub renamed_from: Option<String>,
     pub binary: bool,
+    /// File is part of the change but not present in the working tree
+    /// (e.g. excluded by sparse-checkout)
+    #[serde(rename = "locallyAbsent", default)]
+    pub locally_absent: bool,
+    /// In conflict mode: "both" for conflicted files, "theirs" for files only
+    /// changed on the MERGE_HEAD side
+    #[serde(rename = "conflictSide", default)]
+    pub conflict_side: Option<String>,
+    /// Similarity percentage git assigned to a detected rename (e.g. `R087` -> 87)
+    #[serde(rename = "renameScore", default)]
+    pub rename_score: Option<u8>,
+}
+
+/// A changed file hidden by an `exclude` pattern in `.revi/config.json`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IgnoredFile {
+    #[serde(flatten)]
+    pub file: FileEntry,
+    #[serde(rename = "matchedPattern")]
+    pub matched_pattern: String,
+}
+
+/// A directory or changed file in the sidebar tree view
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TreeNode {
+    pub name: String,
+    pub path: String,
+    #[serde(rename = "isDir")]
+    pub is_dir: bool,
+    pub children: Vec<TreeNode>,
+    /// The changed file, for leaf nodes
+    pub entry: Option<FileEntry>,
+}
+
+/// Default expansion for a file when a session opens
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileExpandPolicy {
+    pub path: String,
+    #[serde(rename = "expandAllHunks")]
+    pub expand_all_hunks: bool,
+    /// Pure renames with no content change need no review
+    #[serde(rename = "autoViewed")]
+    pub auto_viewed: bool,
+}
+
+/// A suggested review step for a file; check state lives in the frontend
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    pub id: String,
+    pub text: String,
+    pub category: String,
+}
+
+/// Number of files whose change size falls in a range, for the size histogram
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DensityBucket {
+    pub label: String,
+    #[serde(rename = "fileCount")]
+    pub file_count: u32,
+    #[serde(rename = "totalChanges")]
+    pub total_changes: u32,
+}
+
+/// Change totals for one top-level directory
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DirectorySummary {
+    /// Top-level directory, or "." for files at the repository root
+    pub directory: String,
+    #[serde(rename = "fileCount")]
+    pub file_count: u32,
+    pub additions: u32,
+    pub deletions: u32,
+}
+
+/// Why git ignores (or doesn't ignore) a path, from `git check-ignore -v`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitignoreInfo {
+    #[serde(rename = "isIgnored")]
+    pub is_ignored: bool,
+    #[serde(rename = "matchingPattern")]
+    pub matching_pattern: Option<String>,
+    /// The .gitignore (or exclude) file and line the pattern came from, e.g. `.gitignore:3`
+    #[serde(rename = "sourceFile")]
+    pub source_file: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -92,6 +283,29 @@ pub struct PersistedState {
     pub head_sha: String,
     pub files: std::collections::HashMap<String, FileState>,
     pub ui: UiState,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub threads: Vec<CommentThread>,
+}
+
+/// A discussion anchored to a line of a file's diff
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentThread {
+    pub id: String,
+    pub path: String,
+    /// Line number on `side` of the diff
+    pub line: u32,
+    /// "old" or "new"
+    pub side: String,
+    pub comments: Vec<Comment>,
+    #[serde(default)]
+    pub resolved: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    pub body: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -107,6 +321,13 @@ pub struct FileState {
     pub collapse_state: CollapseState,
     #[serde(rename = "scrollPosition")]
     pub scroll_position: u32,
+    /// When `viewed` last became true (RFC 3339), stamped by `save_review_state`
+    #[serde(
+        rename = "lastViewedAt",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_viewed_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -136,6 +357,10 @@ pub struct UiState {
 pub enum ComparisonMode {
     /// HEAD vs Working Tree (staged + unstaged + untracked)
     Uncommitted,
+    /// HEAD vs Index (staged changes only)
+    Staged,
+    /// Index vs Working Tree (unstaged changes only)
+    Unstaged,
     /// merge-base(baseBranch)..HEAD
     Branch {
         #[serde(rename = "baseBranch")]
@@ -148,6 +373,8 @@ pub enum ComparisonMode {
         #[serde(rename = "headRef")]
         head_ref: String,
     },
+    /// merge-base(HEAD, MERGE_HEAD)..MERGE_HEAD while a merge is in progress
+    Conflict,
 }
 
 /// Information about a git commit
@@ -159,6 +386,74 @@ pub struct CommitInfo {
     pub message: String,
     pub author: String,
     pub date: String,
+    /// More than one parent means a merge commit
+    #[serde(rename = "parentCount", default)]
+    pub parent_count: u32,
+    /// Start of the message body, for list views; None when there is no body
+    #[serde(rename = "bodyPreview", default)]
+    pub body_preview: Option<String>,
+}
+
+/// A commit's full message, from `get_commit_body`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommitBody {
+    pub subject: String,
+    pub body: String,
+    /// Trailer key -> values, e.g. "Co-authored-by" -> ["A <a@example.com>", ...]
+    pub trailers: HashMap<String, Vec<String>>,
+}
+
+/// `git log` format parsed by `parse_commit_log`. The body is free-form and can
+/// span lines, so each record ends with an ASCII record separator.
+const COMMIT_LOG_FORMAT: &str = "--format=%H%n%h%n%s%n%an%n%aI%n%P%n%b%x1e";
+
+/// Maximum characters of body kept in `CommitInfo.body_preview`
+const BODY_PREVIEW_CHARS: usize = 200;
+
+/// A commit node for rendering a branch graph
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GraphCommit {
+    pub sha: String,
+    #[serde(rename = "shortSha")]
+    pub short_sha: String,
+    pub message: String,
+    pub parents: Vec<String>,
+    /// Branch and tag names pointing at this commit
+    pub refs: Vec<String>,
+    pub author: String,
+    pub date: String,
+}
+
+/// A git tag, with tagger metadata for annotated tags
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagInfo {
+    pub name: String,
+    /// SHA of the tag ref itself (the tag object for annotated tags)
+    pub sha: String,
+    /// SHA of the commit the tag points at
+    #[serde(rename = "targetSha")]
+    pub target_sha: String,
+    #[serde(rename = "isAnnotated")]
+    pub is_annotated: bool,
+    pub tagger: Option<String>,
+    pub date: Option<String>,
+    pub message: Option<String>,
+}
+
+/// GPG signature status of a commit
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SigningStatus {
+    #[serde(rename = "isSigned")]
+    pub is_signed: bool,
+    /// `None` when the signature exists but could not be checked (e.g. missing public key)
+    #[serde(rename = "signatureValid")]
+    pub signature_valid: Option<bool>,
+    #[serde(rename = "signerKey")]
+    pub signer_key: Option<String>,
+    #[serde(rename = "signerIdentity")]
+    pub signer_identity: Option<String>,
+    /// Informational note, e.g. when GPG is not installed
+    pub message: Option<String>,
 }
 
 #[tauri::command]
@@ -172,16 +467,84 @@ pub fn load_session(path: String) -> Result<ReviewManifest, String> {
     Ok(manifest)
 }
 
+/// Load a session file when only its path is known (e.g. `--session <path>`).
+/// Returns the manifest and its verified repo root. If the manifest's `repoRoot`
+/// is no longer a git repository (moved or cloned elsewhere), the root is
+/// inferred from the directories containing the session file.
 #[tauri::command]
-pub fn save_review_state(repo_root: String, state: PersistedState) -> Result<(), String> {
-    let state_dir = Path::new(&repo_root).join(".revi").join("state");
+pub fn load_session_auto_root(session_path: String) -> Result<(ReviewManifest, String), String> {
+    let manifest = load_session(session_path.clone())?;
+
+    if let Ok(root) = get_repo_root(&manifest.repo_root) {
+        return Ok((manifest, root));
+    }
+
+    // Sessions normally live in <repo>/.revi/sessions/, inside the repo itself
+    let root = Path::new(&session_path)
+        .ancestors()
+        .skip(1)
+        .filter(|dir| dir.is_dir())
+        .find_map(|dir| get_repo_root(&dir.to_string_lossy()).ok())
+        .ok_or_else(|| {
+            format!(
+                "Could not find the repository for session {} (recorded root {} is not a git repository)",
+                session_path, manifest.repo_root
+            )
+        })?;
+
+    Ok((manifest, root))
+}
+
+/// Save review state from a window. Fails if another window holds a live lock
+/// on this base/head pair; saving under your own lock refreshes it.
+#[tauri::command]
+pub fn save_review_state(
+    app: AppHandle,
+    repo_root: String,
+    mut state: PersistedState,
+    window_label: Option<String>,
+) -> Result<(), String> {
+    if let Some(lock) = read_session_lock(&repo_root, &state.base_sha, &state.head_sha) {
+        if Some(&lock.window_label) == window_label.as_ref() {
+            let lock_path = session_lock_path(&repo_root, &state.base_sha, &state.head_sha);
+            write_session_lock(&lock_path, &lock.window_label)?;
+        } else if !is_session_lock_stale(&app, &lock) {
+            return Err(format!(
+                "Review state is locked by window '{}' (since {})",
+                lock.window_label, lock.locked_at
+            ));
+        }
+    }
+
+    let previous = load_review_state(
+        repo_root.clone(),
+        state.base_sha.clone(),
+        state.head_sha.clone(),
+    )
+    .ok()
+    .flatten();
+    stamp_last_viewed_at(&mut state, previous.as_ref(), &Utc::now().to_rfc3339());
+
+    // The frontend's autosave doesn't send threads; keep the saved ones
+    if state.threads.is_empty() {
+        if let Some(previous) = previous {
+            state.threads = previous.threads;
+        }
+    }
+
+    write_review_state(&repo_root, &state)
+}
+
+/// Write a review state file without checking the session lock
+fn write_review_state(repo_root: &str, state: &PersistedState) -> Result<(), String> {
+    let state_dir = Path::new(repo_root).join(".revi").join("state");
     fs::create_dir_all(&state_dir)
         .map_err(|e| format!("Failed to create state directory: {}", e))?;
 
     let file_name = format!("{}..{}.json", state.base_sha, state.head_sha);
     let state_path = state_dir.join(file_name);
 
-    let content = serde_json::to_string_pretty(&state)
+    let content = serde_json::to_string_pretty(state)
         .map_err(|e| format!("Failed to serialize state: {}", e))?;
 
     fs::write(&state_path, content).map_err(|e| format!("Failed to write state file: {}", e))?;
@@ -189,6 +552,188 @@ pub fn save_review_state(repo_root: String, state: PersistedState) -> Result<(),
     Ok(())
 }
 
+/// Set `last_viewed_at` on files that just became viewed and carry the saved
+/// value over for files that stay viewed; the frontend may send a stale value
+/// or none at all
+fn stamp_last_viewed_at(state: &mut PersistedState, previous: Option<&PersistedState>, now: &str) {
+    for (path, file) in state.files.iter_mut() {
+        if !file.viewed {
+            file.last_viewed_at = None;
+            continue;
+        }
+
+        file.last_viewed_at = match previous.and_then(|p| p.files.get(path)) {
+            Some(previous_file) if previous_file.viewed => previous_file
+                .last_viewed_at
+                .clone()
+                .or(file.last_viewed_at.take()),
+            Some(_) => Some(now.to_string()),
+            None => file.last_viewed_at.take().or(Some(now.to_string())),
+        };
+    }
+}
+
+/// Contents of a `.revi/state/<base>..<head>.lock` file
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionLock {
+    #[serde(rename = "windowLabel")]
+    pub window_label: String,
+    #[serde(rename = "lockedAt")]
+    pub locked_at: String,
+}
+
+fn session_lock_path(repo_root: &str, base_sha: &str, head_sha: &str) -> PathBuf {
+    Path::new(repo_root)
+        .join(".revi")
+        .join("state")
+        .join(format!("{}..{}.lock", base_sha, head_sha))
+}
+
+fn read_session_lock(repo_root: &str, base_sha: &str, head_sha: &str) -> Option<SessionLock> {
+    let content = fs::read_to_string(session_lock_path(repo_root, base_sha, head_sha)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// A lock is stale once its window has closed, or when it hasn't been refreshed
+/// within `SESSION_LOCK_TTL_MINUTES` (e.g. left behind by a crash)
+fn is_session_lock_stale(app: &AppHandle, lock: &SessionLock) -> bool {
+    !app.webview_windows().contains_key(&lock.window_label)
+        || is_session_lock_expired(lock, Utc::now())
+}
+
+fn is_session_lock_expired(lock: &SessionLock, now: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(&lock.locked_at)
+        .map(|locked_at| {
+            now - locked_at.with_timezone(&Utc) > Duration::minutes(SESSION_LOCK_TTL_MINUTES)
+        })
+        .unwrap_or(true)
+}
+
+/// Overwrite a lock file held by `window_label` with a fresh timestamp
+fn write_session_lock(lock_path: &Path, window_label: &str) -> Result<(), String> {
+    let lock = SessionLock {
+        window_label: window_label.to_string(),
+        locked_at: Utc::now().to_rfc3339(),
+    };
+    let content = serde_json::to_string_pretty(&lock)
+        .map_err(|e| format!("Failed to serialize lock: {}", e))?;
+    fs::write(lock_path, content).map_err(|e| format!("Failed to write lock file: {}", e))
+}
+
+/// Take the write lock on a review state so only one window saves it.
+/// Returns false if another window holds a live lock; re-acquiring your own
+/// lock succeeds and refreshes it, and stale locks are taken over.
+#[tauri::command]
+pub fn save_session_lock(
+    app: AppHandle,
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    window_label: String,
+) -> Result<bool, String> {
+    let lock_path = session_lock_path(&repo_root, &base_sha, &head_sha);
+    if let Some(parent) = lock_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create state directory: {}", e))?;
+    }
+
+    if let Some(lock) = read_session_lock(&repo_root, &base_sha, &head_sha) {
+        if lock.window_label == window_label {
+            write_session_lock(&lock_path, &window_label)?;
+            track_session_lock(&window_label, &lock_path);
+            return Ok(true);
+        }
+        if !is_session_lock_stale(&app, &lock) {
+            return Ok(false);
+        }
+        // Take over the stale lock; `create_new` below still arbitrates a race
+        let _ = fs::remove_file(&lock_path);
+    }
+
+    // `create_new` fails if the file exists, so only one caller can win
+    let mut file = match fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&lock_path)
+    {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            let held_by_us = read_session_lock(&repo_root, &base_sha, &head_sha)
+                .is_some_and(|lock| lock.window_label == window_label);
+            return Ok(held_by_us);
+        }
+        Err(e) => return Err(format!("Failed to create lock file: {}", e)),
+    };
+
+    let lock = SessionLock {
+        window_label,
+        locked_at: Utc::now().to_rfc3339(),
+    };
+    let content = serde_json::to_string_pretty(&lock)
+        .map_err(|e| format!("Failed to serialize lock: {}", e))?;
+    file.write_all(content.as_bytes())
+        .map_err(|e| format!("Failed to write lock file: {}", e))?;
+    track_session_lock(&lock.window_label, &lock_path);
+
+    Ok(true)
+}
+
+fn track_session_lock(window_label: &str, lock_path: &Path) {
+    HELD_SESSION_LOCKS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(window_label.to_string())
+        .or_default()
+        .insert(lock_path.to_path_buf());
+}
+
+/// Remove every lock file still held by a window. Called when the window is destroyed.
+pub fn release_window_session_locks(window_label: &str) {
+    let lock_paths = HELD_SESSION_LOCKS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(window_label)
+        .unwrap_or_default();
+
+    for lock_path in lock_paths {
+        let held_by_window = fs::read_to_string(&lock_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<SessionLock>(&content).ok())
+            .is_some_and(|lock| lock.window_label == window_label);
+        if held_by_window {
+            let _ = fs::remove_file(&lock_path);
+        }
+    }
+}
+
+/// Release a review state lock held by `window_label`. Locks held by other
+/// windows are left in place.
+#[tauri::command]
+pub fn release_session_lock(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    window_label: String,
+) -> Result<(), String> {
+    match read_session_lock(&repo_root, &base_sha, &head_sha) {
+        Some(lock) if lock.window_label != window_label => {
+            Err(format!("Lock is held by window '{}'", lock.window_label))
+        }
+        Some(_) => {
+            let lock_path = session_lock_path(&repo_root, &base_sha, &head_sha);
+            if let Some(paths) = HELD_SESSION_LOCKS
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .get_mut(&window_label)
+            {
+                paths.remove(&lock_path);
+            }
+            fs::remove_file(&lock_path).map_err(|e| format!("Failed to remove lock file: {}", e))
+        }
+        None => Ok(()),
+    }
+}
+
 #[tauri::command]
 pub fn load_review_state(
     repo_root: String,
@@ -347,92 +892,623 @@ pub fn recover_state(
     }))
 }
 
-/// Create a new review session from a repository path
-/// This is used when the app is launched directly and the user picks a folder
+/// Number of entries returned in `ReviewAnalytics::most_reviewed`
+const MOST_REVIEWED_LIMIT: usize = 10;
+
+/// Aggregate statistics across all persisted review states for a repo
+#[derive(Debug, Serialize)]
+pub struct ReviewAnalytics {
+    #[serde(rename = "sessionCount")]
+    pub session_count: u32,
+    #[serde(rename = "avgFiles")]
+    pub avg_files: f64,
+    #[serde(rename = "avgViewedPct")]
+    pub avg_viewed_pct: f64,
+    /// (path, number of sessions in which the file was marked viewed), most viewed first
+    #[serde(rename = "mostReviewed")]
+    pub most_reviewed: Vec<(String, u32)>,
+    #[serde(rename = "totalAdditions")]
+    pub total_additions: u64,
+    #[serde(rename = "totalDeletions")]
+    pub total_deletions: u64,
+    #[serde(rename = "uniqueShaCount")]
+    pub unique_sha_count: u32,
+}
+
+/// Compute review analytics from every state file in .revi/state/.
+/// Unreadable or malformed state files are skipped.
 #[tauri::command]
-pub fn create_session_from_repo(
-    repo_path: String,
-    base_ref: Option<String>,
-    mode: Option<ComparisonMode>,
-) -> Result<ReviewManifest, String> {
-    // Verify it's a git repository
-    let repo_root = get_repo_root(&repo_path)?;
+pub fn get_review_analytics(repo_root: String) -> Result<ReviewAnalytics, String> {
+    let state_dir = Path::new(&repo_root).join(".revi").join("state");
 
-    // Get current branch (for display purposes)
-    let current_branch = get_current_branch(&repo_root);
+    let mut states: Vec<PersistedState> = Vec::new();
+    if state_dir.exists() {
+        let entries =
+            fs::read_dir(&state_dir).map_err(|e| format!("Failed to read state dir: {}", e))?;
 
-    // If mode is explicitly provided, use it
-    if let Some(comparison_mode) = mode {
-        return create_session_with_mode(&repo_root, comparison_mode, current_branch);
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            if let Ok(state) = serde_json::from_str::<PersistedState>(&content) {
+                states.push(state);
+            }
+        }
     }
 
-    // Auto-detect mode: check if there are uncommitted changes
-    let has_uncommitted = has_uncommitted_changes(&repo_root)?;
+    let mut total_files: usize = 0;
+    let mut viewed_pct_sum: f64 = 0.0;
+    let mut total_additions: u64 = 0;
+    let mut total_deletions: u64 = 0;
+    let mut review_counts: HashMap<String, u32> = HashMap::new();
+    let mut shas: HashSet<&str> = HashSet::new();
+
+    for state in &states {
+        shas.insert(&state.base_sha);
+        shas.insert(&state.head_sha);
+        total_files += state.files.len();
+
+        let mut viewed = 0usize;
+        for (path, file) in &state.files {
+            if !file.viewed {
+                continue;
+            }
+            viewed += 1;
+            total_additions += file.diff_stats.additions as u64;
+            total_deletions += file.diff_stats.deletions as u64;
+            *review_counts.entry(path.clone()).or_insert(0) += 1;
+        }
 
-    if has_uncommitted {
-        // Show uncommitted changes: HEAD vs working tree
-        create_session_with_mode(&repo_root, ComparisonMode::Uncommitted, current_branch)
+        if !state.files.is_empty() {
+            viewed_pct_sum += viewed as f64 / state.files.len() as f64 * 100.0;
+        }
+    }
+
+    let mut most_reviewed: Vec<(String, u32)> = review_counts.into_iter().collect();
+    most_reviewed.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    most_reviewed.truncate(MOST_REVIEWED_LIMIT);
+
+    let session_count = states.len();
+    let (avg_files, avg_viewed_pct) = if session_count == 0 {
+        (0.0, 0.0)
     } else {
-        // No uncommitted changes - fall back to comparing commits (branch mode)
-        // Use provided base_ref or auto-detect
-        let base_branch = base_ref.unwrap_or_else(|| detect_default_base_branch(&repo_root));
-        create_session_with_mode(
-            &repo_root,
-            ComparisonMode::Branch {
-                base_branch: base_branch,
-            },
-            current_branch,
+        (
+            total_files as f64 / session_count as f64,
+            viewed_pct_sum / session_count as f64,
         )
-    }
+    };
+
+    Ok(ReviewAnalytics {
+        session_count: session_count as u32,
+        avg_files,
+        avg_viewed_pct,
+        most_reviewed,
+        total_additions,
+        total_deletions,
+        unique_sha_count: shas.len() as u32,
+    })
 }
 
-/// Create a session with an explicit comparison mode
-fn create_session_with_mode(
-    repo_root: &str,
-    mode: ComparisonMode,
-    current_branch: Option<String>,
-) -> Result<ReviewManifest, String> {
-    let (base, head, files, comparison_mode) = match &mode {
-        ComparisonMode::Uncommitted => {
-            let base = get_ref_info(repo_root, "HEAD")?;
-            let head = RefInfo {
-                ref_name: "Working Tree".to_string(),
-                sha: "WORKING_TREE".to_string(),
+/// One session in `get_all_sessions_overview`
+#[derive(Debug, Serialize)]
+pub struct SessionOverview {
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+    pub name: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    pub status: ReviewStatus,
+    #[serde(rename = "totalFiles")]
+    pub total_files: u32,
+    #[serde(rename = "viewedFiles")]
+    pub viewed_files: u32,
+    /// Additions plus deletions across all files
+    #[serde(rename = "totalChanges")]
+    pub total_changes: u32,
+    /// When the session's review state was last saved
+    #[serde(rename = "lastActivity")]
+    pub last_activity: Option<String>,
+}
+
+/// Summarize every session in .revi/sessions/ for a dashboard, joined with its
+/// review state by base/head SHAs. Most recently active first; sessions never
+/// reviewed come last, newest first. Unreadable files are skipped.
+#[tauri::command]
+pub fn get_all_sessions_overview(repo_root: String) -> Result<Vec<SessionOverview>, String> {
+    let revi_dir = Path::new(&repo_root).join(".revi");
+
+    // (base_sha, head_sha) -> (state, last modified)
+    let mut states: HashMap<(String, String), (PersistedState, Option<String>)> = HashMap::new();
+    let state_dir = revi_dir.join("state");
+    if state_dir.exists() {
+        let entries =
+            fs::read_dir(&state_dir).map_err(|e| format!("Failed to read state dir: {}", e))?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
             };
-            let files = get_uncommitted_files(repo_root)?;
-            (base, head, files, mode)
-        }
-        ComparisonMode::Branch { base_branch } => {
-            // Get merge-base with the specified branch
-            let base = match get_merge_base(repo_root, base_branch) {
-                Ok(merge_base_sha) => RefInfo {
-                    ref_name: base_branch.clone(),
-                    sha: merge_base_sha,
-                },
-                Err(_) => {
-                    // Fallback: try to resolve the branch directly
-                    get_ref_info(repo_root, base_branch)?
-                }
+            let Ok(state) = serde_json::from_str::<PersistedState>(&content) else {
+                continue;
             };
-            let head = get_ref_info(repo_root, "HEAD")?;
-            let files = get_changed_files(repo_root, &base.sha, &head.sha)?;
-            (base, head, files, mode)
+            let modified = path
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .map(|time| chrono::DateTime::<Utc>::from(time).to_rfc3339());
+            states.insert(
+                (state.base_sha.clone(), state.head_sha.clone()),
+                (state, modified),
+            );
         }
-        ComparisonMode::Custom { base_ref, head_ref } => {
-            let base = get_ref_info(repo_root, base_ref)?;
-            let head = get_ref_info(repo_root, head_ref)?;
-            let files = get_changed_files(repo_root, &base.sha, &head.sha)?;
-            (base, head, files, mode)
+    }
+
+    let mut overviews = Vec::new();
+    let sessions_dir = revi_dir.join("sessions");
+    if sessions_dir.exists() {
+        let entries = fs::read_dir(&sessions_dir)
+            .map_err(|e| format!("Failed to read sessions dir: {}", e))?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let Ok(manifest) = load_session(path.to_string_lossy().into_owned()) else {
+                continue;
+            };
+
+            let state = states.get(&(manifest.base.sha.clone(), manifest.head.sha.clone()));
+            let viewed_files = manifest
+                .files
+                .iter()
+                .filter(|file| {
+                    state
+                        .and_then(|(s, _)| s.files.get(&file.path))
+                        .is_some_and(|f| f.viewed)
+                })
+                .count() as u32;
+
+            overviews.push(SessionOverview {
+                session_id: manifest.session_id,
+                name: manifest.name,
+                created_at: manifest.created_at,
+                status: manifest.status,
+                total_files: manifest.files.len() as u32,
+                viewed_files,
+                total_changes: manifest
+                    .files
+                    .iter()
+                    .map(|f| f.additions + f.deletions)
+                    .sum(),
+                last_activity: state.and_then(|(_, modified)| modified.clone()),
+            });
         }
-    };
+    }
 
-    // Generate session ID
-    let session_id = nanoid!(12);
+    // All timestamps are RFC 3339 in UTC, so they sort as strings
+    overviews.sort_by(|a, b| {
+        b.last_activity
+            .cmp(&a.last_activity)
+            .then_with(|| b.created_at.cmp(&a.created_at))
+    });
 
-    // Create manifest
-    let manifest = ReviewManifest {
-        version: 1,
-        session_id: session_id.clone(),
+    Ok(overviews)
+}
+
+/// How far a review has progressed, by file count and by change volume
+#[derive(Debug, Serialize)]
+pub struct ReviewProgress {
+    #[serde(rename = "viewedFiles")]
+    pub viewed_files: u32,
+    #[serde(rename = "totalFiles")]
+    pub total_files: u32,
+    /// Percentage of files marked viewed
+    #[serde(rename = "pctComplete")]
+    pub pct_complete: f64,
+    /// Percentage of changed lines (additions + deletions) in viewed files
+    #[serde(rename = "pctCompleteByVolume")]
+    pub pct_complete_by_volume: f64,
+    #[serde(rename = "viewedAdditions")]
+    pub viewed_additions: u32,
+    #[serde(rename = "totalAdditions")]
+    pub total_additions: u32,
+    #[serde(rename = "viewedDeletions")]
+    pub viewed_deletions: u32,
+    #[serde(rename = "totalDeletions")]
+    pub total_deletions: u32,
+}
+
+/// Compute review progress for a base/head pair from its session manifest and
+/// persisted state. Files missing from the state count as not viewed.
+#[tauri::command]
+pub fn get_session_reviewed_pct(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+) -> Result<ReviewProgress, String> {
+    let (manifest, state) = load_session_for_range(&repo_root, &base_sha, &head_sha)?;
+
+    let mut progress = ReviewProgress {
+        viewed_files: 0,
+        total_files: manifest.files.len() as u32,
+        pct_complete: 0.0,
+        pct_complete_by_volume: 0.0,
+        viewed_additions: 0,
+        total_additions: 0,
+        viewed_deletions: 0,
+        total_deletions: 0,
+    };
+
+    for file in &manifest.files {
+        progress.total_additions += file.additions;
+        progress.total_deletions += file.deletions;
+
+        let viewed = state
+            .as_ref()
+            .and_then(|s| s.files.get(&file.path))
+            .is_some_and(|f| f.viewed);
+        if viewed {
+            progress.viewed_files += 1;
+            progress.viewed_additions += file.additions;
+            progress.viewed_deletions += file.deletions;
+        }
+    }
+
+    if progress.total_files > 0 {
+        progress.pct_complete = progress.viewed_files as f64 / progress.total_files as f64 * 100.0;
+    }
+    let total_volume = progress.total_additions + progress.total_deletions;
+    if total_volume > 0 {
+        let viewed_volume = progress.viewed_additions + progress.viewed_deletions;
+        progress.pct_complete_by_volume = viewed_volume as f64 / total_volume as f64 * 100.0;
+    }
+
+    Ok(progress)
+}
+
+/// Load the manifest and persisted state (if any) for a base/head pair,
+/// preferring the session the state was saved under
+fn load_session_for_range(
+    repo_root: &str,
+    base_sha: &str,
+    head_sha: &str,
+) -> Result<(ReviewManifest, Option<PersistedState>), String> {
+    let state = load_review_state(
+        repo_root.to_string(),
+        base_sha.to_string(),
+        head_sha.to_string(),
+    )?;
+    let manifest = match &state {
+        Some(state) => read_manifest(repo_root, &state.session_id).ok(),
+        None => None,
+    };
+    let manifest = match manifest {
+        Some(manifest) => manifest,
+        None => find_manifest_for_range(repo_root, base_sha, head_sha)?
+            .ok_or_else(|| format!("No session found for {}..{}", base_sha, head_sha))?,
+    };
+
+    Ok((manifest, state))
+}
+
+/// Write per-file review progress for a base/head pair to a CSV file for
+/// spreadsheets. `last_viewed_at` is empty for unviewed files and for files
+/// viewed before the timestamp was recorded.
+#[tauri::command]
+pub fn export_review_progress_csv(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    output_path: String,
+) -> Result<(), String> {
+    let (manifest, state) = load_session_for_range(&repo_root, &base_sha, &head_sha)?;
+
+    let mut csv = String::from(
+        "file_path,status,additions,deletions,viewed,last_viewed_at,scroll_position,comment_count\n",
+    );
+    for file in &manifest.files {
+        let file_state = state.as_ref().and_then(|s| s.files.get(&file.path));
+        let comment_count = state.as_ref().map_or(0, |s| {
+            s.threads
+                .iter()
+                .filter(|thread| thread.path == file.path)
+                .map(|thread| thread.comments.len())
+                .sum::<usize>()
+        });
+
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{}\n",
+            csv_field(&file.path),
+            csv_field(&file.status),
+            file.additions,
+            file.deletions,
+            file_state.is_some_and(|f| f.viewed),
+            file_state
+                .and_then(|f| f.last_viewed_at.as_deref())
+                .unwrap_or(""),
+            file_state.map_or(0, |f| f.scroll_position),
+            comment_count,
+        ));
+    }
+
+    fs::write(&output_path, csv).map_err(|e| format!("Failed to write CSV: {}", e))
+}
+
+/// Quote a CSV field if it contains a delimiter, quote, or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Files of a session whose path matches a glob, without modifying the session.
+/// Patterns without a `/` match the file name anywhere (e.g. `*.test.ts`).
+#[tauri::command]
+pub fn get_session_file_subset(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    glob_pattern: String,
+) -> Result<Vec<FileEntry>, String> {
+    let (manifest, _) = load_session_for_range(&repo_root, &base_sha, &head_sha)?;
+    let patterns = [glob_pattern];
+    Ok(manifest
+        .files
+        .into_iter()
+        .filter(|file| matching_exclude_pattern(&file.path, &patterns).is_some())
+        .collect())
+}
+
+/// Files of a session with the given extension (with or without the leading
+/// dot, case-insensitive)
+#[tauri::command]
+pub fn get_session_files_by_extension(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    extension: String,
+) -> Result<Vec<FileEntry>, String> {
+    let (manifest, _) = load_session_for_range(&repo_root, &base_sha, &head_sha)?;
+    let extension = extension.trim_start_matches('.');
+    Ok(manifest
+        .files
+        .into_iter()
+        .filter(|file| {
+            Path::new(&file.path)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+        })
+        .collect())
+}
+
+/// Find the most recently created session manifest comparing `base_sha` to `head_sha`
+fn find_manifest_for_range(
+    repo_root: &str,
+    base_sha: &str,
+    head_sha: &str,
+) -> Result<Option<ReviewManifest>, String> {
+    let sessions_dir = Path::new(repo_root).join(".revi").join("sessions");
+    if !sessions_dir.exists() {
+        return Ok(None);
+    }
+
+    let entries =
+        fs::read_dir(&sessions_dir).map_err(|e| format!("Failed to read sessions dir: {}", e))?;
+
+    Ok(entries
+        .flatten()
+        .filter(|entry| entry.path().extension().and_then(|e| e.to_str()) == Some("json"))
+        .filter_map(|entry| load_session(entry.path().to_string_lossy().into_owned()).ok())
+        .filter(|m| m.base.sha == base_sha && m.head.sha == head_sha)
+        .max_by(|a, b| a.created_at.cmp(&b.created_at)))
+}
+
+/// Create a new review session from a repository path
+/// This is used when the app is launched directly and the user picks a folder
+#[tauri::command]
+pub fn create_session_from_repo(
+    repo_path: String,
+    base_ref: Option<String>,
+    mode: Option<ComparisonMode>,
+) -> Result<ReviewManifest, String> {
+    // Verify it's a git repository
+    let repo_root = get_repo_root(&repo_path)?;
+
+    // Get current branch (for display purposes)
+    let current_branch = get_current_branch(&repo_root);
+
+    // If mode is explicitly provided, use it
+    let comparison_mode = match mode {
+        Some(comparison_mode) => comparison_mode,
+        None => detect_comparison_mode(&repo_root, base_ref)?,
+    };
+
+    create_session_with_mode(&repo_root, comparison_mode, current_branch, None)
+}
+
+/// Pick a comparison mode for a repository when none was requested.
+/// Conflict mode is only used when requested explicitly.
+fn detect_comparison_mode(
+    repo_root: &str,
+    base_ref: Option<String>,
+) -> Result<ComparisonMode, String> {
+    // Auto-detect mode: check if there are uncommitted changes
+    let has_uncommitted = has_uncommitted_changes(repo_root)?;
+
+    if has_uncommitted {
+        // Show uncommitted changes: HEAD vs working tree
+        Ok(ComparisonMode::Uncommitted)
+    } else {
+        // No uncommitted changes - fall back to comparing commits (branch mode)
+        // Use provided base_ref or auto-detect
+        let base_branch = base_ref.unwrap_or_else(|| detect_default_base_branch(repo_root));
+        Ok(ComparisonMode::Branch {
+            base_branch: base_branch,
+        })
+    }
+}
+
+/// Re-read an existing session's files and refs under its comparison mode,
+/// keeping its session id and everything the user attached to it (notes,
+/// status, name, tags, PR info)
+#[tauri::command]
+pub fn refresh_session(repo_root: String, session_id: String) -> Result<ReviewManifest, String> {
+    let previous = read_manifest(&repo_root, &session_id)?;
+    let repo_root = get_repo_root(&repo_root)?;
+
+    let mode = match previous.comparison_mode {
+        Some(mode) => mode,
+        None => detect_comparison_mode(&repo_root, None)?,
+    };
+    let current_branch = get_current_branch(&repo_root);
+    let manifest = ReviewManifest {
+        session_id: previous.session_id,
+        created_at: previous.created_at,
+        notes: previous.notes,
+        status: previous.status,
+        name: previous.name,
+        tags: previous.tags,
+        is_single_commit: previous.is_single_commit,
+        ..build_session_manifest(&repo_root, mode, current_branch, previous.pr_info)?
+    };
+    write_manifest(&repo_root, &manifest.session_id, &manifest)?;
+
+    Ok(manifest)
+}
+
+/// Create a session with an explicit comparison mode
+fn create_session_with_mode(
+    repo_root: &str,
+    mode: ComparisonMode,
+    current_branch: Option<String>,
+    pr_info: Option<PrInfo>,
+) -> Result<ReviewManifest, String> {
+    let manifest = build_session_manifest(repo_root, mode, current_branch, pr_info)?;
+
+    // Write manifest to .revi/sessions/
+    write_manifest(repo_root, &manifest.session_id, &manifest)?;
+
+    Ok(manifest)
+}
+
+/// Resolve refs and changed files for a comparison mode into a new manifest
+/// with a fresh session id, without writing it
+fn build_session_manifest(
+    repo_root: &str,
+    mode: ComparisonMode,
+    current_branch: Option<String>,
+    pr_info: Option<PrInfo>,
+) -> Result<ReviewManifest, String> {
+    let (base, head, files, comparison_mode) = match &mode {
+        ComparisonMode::Uncommitted => {
+            let base = get_ref_info(repo_root, "HEAD")?;
+            let head = RefInfo {
+                ref_name: "Working Tree".to_string(),
+                sha: "WORKING_TREE".to_string(),
+            };
+            let files = get_uncommitted_files(repo_root)?;
+            (base, head, files, mode)
+        }
+        ComparisonMode::Staged => {
+            let base = get_ref_info(repo_root, "HEAD")?;
+            let head = RefInfo {
+                ref_name: "Index".to_string(),
+                sha: "INDEX".to_string(),
+            };
+            let files = get_uncommitted_staged_files(repo_root.to_string())?;
+            (base, head, files, mode)
+        }
+        ComparisonMode::Unstaged => {
+            let base = RefInfo {
+                ref_name: "Index".to_string(),
+                sha: "INDEX".to_string(),
+            };
+            let head = RefInfo {
+                ref_name: "Working Tree".to_string(),
+                sha: "WORKING_TREE".to_string(),
+            };
+            let files = get_uncommitted_unstaged_files(repo_root.to_string())?;
+            (base, head, files, mode)
+        }
+        ComparisonMode::Branch { base_branch } => {
+            // Get merge-base with the specified branch
+            let base = match get_merge_base(repo_root, base_branch) {
+                Ok(merge_base_sha) => RefInfo {
+                    ref_name: base_branch.clone(),
+                    sha: merge_base_sha,
+                },
+                Err(_) => {
+                    // Fallback: try to resolve the branch directly
+                    get_ref_info(repo_root, base_branch)?
+                }
+            };
+            let head = get_ref_info(repo_root, "HEAD")?;
+            let files = get_changed_files(repo_root, &base.sha, &head.sha)?;
+            (base, head, files, mode)
+        }
+        ComparisonMode::Custom { base_ref, head_ref } => {
+            let base = get_ref_info(repo_root, base_ref)?;
+            let head = get_ref_info(repo_root, head_ref)?;
+            let files = get_changed_files(repo_root, &base.sha, &head.sha)?;
+            (base, head, files, mode)
+        }
+        ComparisonMode::Conflict => {
+            if !is_merge_in_progress(repo_root) {
+                return Err("No merge in progress".to_string());
+            }
+            // Ours is HEAD, theirs is MERGE_HEAD; diff theirs against the merge-base
+            let base = RefInfo {
+                ref_name: "merge-base".to_string(),
+                sha: get_merge_base(repo_root, "MERGE_HEAD")?,
+            };
+            let head = get_ref_info(repo_root, "MERGE_HEAD")?;
+            let files = get_merge_files(repo_root, &base.sha, &head.sha)?;
+            (base, head, files, mode)
+        }
+    };
+
+    // Hide files matching `exclude` in .revi/config.json (listed by get_ignored_files)
+    let files = without_excluded_files(repo_root, files);
+
+    // Hide files outside the sparse-checkout cone
+    let sparse_patterns = get_sparse_checkout_patterns(repo_root.to_string())?;
+    let is_sparse = sparse_patterns.is_some();
+    let (mut files, skipped_count) = match sparse_patterns {
+        Some(patterns) => {
+            let total = files.len();
+            let kept: Vec<FileEntry> = files
+                .into_iter()
+                .filter(|f| matches_sparse_patterns(&f.path, &patterns))
+                .collect();
+            let skipped = (total - kept.len()) as u32;
+            (kept, skipped)
+        }
+        None => (files, 0),
+    };
+    // Only a working-tree or index head reads from disk, so only then can a
+    // file missing from a sparse checkout fail to load
+    if is_sparse && (head.sha == "WORKING_TREE" || head.sha == "INDEX") {
+        for file in &mut files {
+            file.locally_absent =
+                file.status != "deleted" && !Path::new(repo_root).join(&file.path).exists();
+        }
+    }
+
+    Ok(ReviewManifest {
+        version: 1,
+        session_id: nanoid!(12),
         repo_root: repo_root.to_string(),
         base,
         head,
@@ -443,12 +1519,721 @@ fn create_session_with_mode(
         files,
         created_at: Utc::now().to_rfc3339(),
         comparison_mode: Some(comparison_mode),
+        pr_info,
+        skipped_count,
+        notes: None,
+        status: ReviewStatus::Pending,
+        name: None,
+        tags: Vec::new(),
+        is_single_commit: false,
+    })
+}
+
+/// Create a session reviewing a single commit against its first parent
+#[tauri::command]
+pub fn get_diff_for_commit(
+    repo_root: String,
+    commit_sha: String,
+) -> Result<ReviewManifest, String> {
+    let repo_root = get_repo_root(&repo_root)?;
+    let current_branch = get_current_branch(&repo_root);
+
+    let mode = ComparisonMode::Custom {
+        base_ref: format!("{}^", commit_sha),
+        head_ref: commit_sha,
+    };
+    let mut manifest = create_session_with_mode(&repo_root, mode, current_branch, None)?;
+    manifest.is_single_commit = true;
+    write_manifest(&repo_root, &manifest.session_id, &manifest)?;
+
+    Ok(manifest)
+}
+
+/// Parse a GitHub PR URL (`https://github.com/<owner>/<repo>/pull/<number>[/...]`)
+/// into `(owner, repo, number)`
+fn parse_pr_url(pr_url: &str) -> Option<(String, String, u64)> {
+    let rest = pr_url
+        .trim()
+        .strip_prefix("https://")
+        .or_else(|| pr_url.trim().strip_prefix("http://"))?;
+    let rest = rest.strip_prefix("www.").unwrap_or(rest);
+    let rest = rest.strip_prefix("github.com/")?;
+
+    let mut parts = rest.split('/');
+    let owner = parts.next().filter(|p| !p.is_empty())?;
+    let repo = parts.next().filter(|p| !p.is_empty())?;
+    if parts.next()? != "pull" {
+        return None;
+    }
+    let number = parts.next()?.split(['?', '#']).next()?.parse().ok()?;
+
+    Some((owner.to_string(), repo.to_string(), number))
+}
+
+/// Make sure a commit exists locally, fetching `refspec` from `remote` (a
+/// remote name or URL) if it doesn't
+fn ensure_commit_available(
+    repo_root: &str,
+    sha: &str,
+    remote: &str,
+    refspec: &str,
+) -> Result<(), String> {
+    let has_commit = Command::new("git")
+        .args(["cat-file", "-e", &format!("{}^{{commit}}", sha)])
+        .current_dir(repo_root)
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false);
+
+    if has_commit {
+        return Ok(());
+    }
+
+    let output = Command::new("git")
+        .args(["fetch", remote, refspec])
+        .current_dir(repo_root)
+        .output()
+        .map_err(|e| format!("Failed to fetch {}: {}", refspec, e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git fetch {} failed: {}", refspec, stderr.trim()));
+    }
+
+    Ok(())
+}
+
+/// Create a session comparing the base and head commits of a GitHub pull request
+/// and remember it as the last opened session.
+/// Uses `GITHUB_TOKEN` for authentication when set (required for private repos).
+#[tauri::command]
+pub async fn create_session_from_pr_url(
+    app: AppHandle,
+    repo_root: String,
+    pr_url: String,
+) -> Result<ReviewManifest, String> {
+    let (owner, repo, number) =
+        parse_pr_url(&pr_url).ok_or_else(|| format!("Not a GitHub PR URL: {}", pr_url))?;
+
+    let api_url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/{}",
+        owner, repo, number
+    );
+    let mut request = reqwest::Client::new()
+        .get(&api_url)
+        .header("User-Agent", "revi")
+        .header("Accept", "application/vnd.github+json");
+    if let Ok(token) = env::var("GITHUB_TOKEN") {
+        request = request.bearer_auth(token);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch PR: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("GitHub API returned {}", response.status()));
+    }
+
+    let pr: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse PR response: {}", e))?;
+
+    let field = |pointer: &str| {
+        pr.pointer(pointer)
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string())
+            .ok_or_else(|| format!("PR response missing {}", pointer))
+    };
+    let base_sha = field("/base/sha")?;
+    let head_sha = field("/head/sha")?;
+    let base_branch = field("/base/ref")?;
+    let pr_info = PrInfo {
+        title: field("/title")?,
+        number,
+        html_url: field("/html_url")?,
+        author: field("/user/login")?,
+    };
+
+    // Fetching the PR refs and diffing can take a while; keep it off the async runtime
+    let manifest = tauri::async_runtime::spawn_blocking(move || {
+        let repo_root = get_repo_root(&repo_root)?;
+
+        // Fetch from the PR's repository unless origin already points at it
+        let pr_remote = format!("https://github.com/{}/{}.git", owner, repo);
+        let remote = match get_origin_url(&repo_root) {
+            Some(url) if normalize_remote_url(&url) == normalize_remote_url(&pr_remote) => {
+                "origin".to_string()
+            }
+            _ => pr_remote,
+        };
+        ensure_commit_available(&repo_root, &base_sha, &remote, &base_branch)?;
+        ensure_commit_available(
+            &repo_root,
+            &head_sha,
+            &remote,
+            &format!("pull/{}/head", number),
+        )?;
+
+        let current_branch = get_current_branch(&repo_root);
+        create_session_with_mode(
+            &repo_root,
+            ComparisonMode::Custom {
+                base_ref: base_sha,
+                head_ref: head_sha,
+            },
+            current_branch,
+            Some(pr_info),
+        )
+    })
+    .await
+    .map_err(|e| format!("Failed to create PR session: {}", e))??;
+
+    save_last_session(
+        app,
+        manifest.repo_root.clone(),
+        Some(manifest.base.ref_name.clone()),
+    )?;
+
+    Ok(manifest)
+}
+
+/// Read sparse-checkout patterns, or `None` if sparse-checkout is not enabled
+#[tauri::command]
+pub fn get_sparse_checkout_patterns(repo_root: String) -> Result<Option<Vec<String>>, String> {
+    let enabled = Command::new("git")
+        .args(["config", "--bool", "core.sparseCheckout"])
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to read git config: {}", e))?;
+
+    if String::from_utf8_lossy(&enabled.stdout).trim() != "true" {
+        return Ok(None);
+    }
+
+    // Resolve via git so linked worktrees find their own info/ directory
+    let git_path = Command::new("git")
+        .args(["rev-parse", "--git-path", "info/sparse-checkout"])
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to locate sparse-checkout file: {}", e))?;
+
+    if !git_path.status.success() {
+        return Err("Failed to locate sparse-checkout file".to_string());
+    }
+
+    let sparse_path = Path::new(&repo_root).join(String::from_utf8_lossy(&git_path.stdout).trim());
+    let content = match fs::read_to_string(&sparse_path) {
+        Ok(content) => content,
+        Err(_) => return Ok(None),
+    };
+
+    let patterns = content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.to_string())
+        .collect();
+
+    Ok(Some(patterns))
+}
+
+/// Check whether a path is inside the sparse-checkout cone.
+/// Patterns use gitignore syntax (which cone-mode patterns are a subset of);
+/// the last matching pattern wins and `!` excludes.
+fn matches_sparse_patterns(path: &str, patterns: &[String]) -> bool {
+    let mut included = false;
+
+    for raw in patterns {
+        let (negated, pattern) = match raw.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, raw.as_str()),
+        };
+        let (dir_only, pattern) = match pattern.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, pattern),
+        };
+        let anchored = pattern.contains('/');
+        let pattern = pattern.trim_start_matches('/');
+
+        // A pattern matching a directory matches everything inside it,
+        // so test each ancestor directory as well as the path itself
+        let mut candidates: Vec<&str> = path
+            .match_indices('/')
+            .map(|(idx, _)| &path[..idx])
+            .collect();
+        if !dir_only {
+            candidates.push(path);
+        }
+
+        let matched = candidates.iter().any(|candidate| {
+            if anchored {
+                glob_match(pattern, candidate)
+            } else {
+                let name = candidate.rsplit('/').next().unwrap_or(candidate);
+                glob_match(pattern, name)
+            }
+        });
+
+        if matched {
+            included = !negated;
+        }
+    }
+
+    included
+}
+
+/// Minimal glob matching: `*` and `?` stay within a path segment, `**` crosses segments
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.as_bytes();
+    let text = text.as_bytes();
+
+    fn helper(p: &[u8], t: &[u8]) -> bool {
+        match p.first() {
+            None => t.is_empty(),
+            Some(b'*') if p.get(1) == Some(&b'*') => {
+                let rest = p[2..].strip_prefix(b"/").unwrap_or(&p[2..]);
+                (0..=t.len()).any(|i| helper(rest, &t[i..]))
+            }
+            Some(b'*') => {
+                let rest = &p[1..];
+                for i in 0..=t.len() {
+                    if helper(rest, &t[i..]) {
+                        return true;
+                    }
+                    if i < t.len() && t[i] == b'/' {
+                        break;
+                    }
+                }
+                false
+            }
+            Some(b'?') => !t.is_empty() && t[0] != b'/' && helper(&p[1..], &t[1..]),
+            Some(&c) => !t.is_empty() && t[0] == c && helper(&p[1..], &t[1..]),
+        }
+    }
+
+    helper(pattern, text)
+}
+
+/// Load `.revi/config.json`; None if it's missing or not valid JSON
+fn read_repo_config(repo_root: &str) -> Option<serde_json::Value> {
+    fs::read_to_string(Path::new(repo_root).join(".revi").join("config.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+/// Read the `renameThreshold` similarity percentage from `.revi/config.json`
+pub fn read_rename_threshold(repo_root: &str) -> Option<u8> {
+    let threshold = read_repo_config(repo_root)?["renameThreshold"].as_u64()?;
+    Some(threshold.min(100) as u8)
+}
+
+/// `--find-renames` flag for a similarity threshold; git's default (50%) when None
+pub fn find_renames_arg(threshold: Option<u8>) -> String {
+    match threshold {
+        Some(n) => format!("--find-renames={}%", n.min(100)),
+        None => "--find-renames".to_string(),
+    }
+}
+
+/// Read the `exclude` globs from `.revi/config.json`
+fn read_exclude_patterns(repo_root: &str) -> Vec<String> {
+    let Some(config) = read_repo_config(repo_root) else {
+        return Vec::new();
+    };
+
+    config["exclude"]
+        .as_array()
+        .map(|patterns| {
+            patterns
+                .iter()
+                .filter_map(|p| p.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Find the first exclude pattern matching a path. Patterns without a `/`
+/// match the file name anywhere in the tree (e.g. `*.generated.ts`).
+fn matching_exclude_pattern<'a>(path: &str, patterns: &'a [String]) -> Option<&'a String> {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    patterns.iter().find(|pattern| {
+        if pattern.contains('/') {
+            glob_match(pattern, path)
+        } else {
+            glob_match(pattern, file_name)
+        }
+    })
+}
+
+/// Drop files matching an `exclude` pattern in `.revi/config.json`
+fn without_excluded_files(repo_root: &str, files: Vec<FileEntry>) -> Vec<FileEntry> {
+    let patterns = read_exclude_patterns(repo_root);
+    if patterns.is_empty() {
+        return files;
+    }
+
+    files
+        .into_iter()
+        .filter(|file| matching_exclude_pattern(&file.path, &patterns).is_none())
+        .collect()
+}
+
+/// List changed files that the `exclude` patterns in `.revi/config.json` hide,
+/// with the pattern responsible for each, to help debug the ignore configuration
+#[tauri::command]
+pub fn get_ignored_files(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+) -> Result<Vec<IgnoredFile>, String> {
+    let patterns = read_exclude_patterns(&repo_root);
+    if patterns.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let files = get_files_for_range(&repo_root, &base_sha, &head_sha)?;
+
+    Ok(files
+        .into_iter()
+        .filter_map(|file| {
+            let pattern = matching_exclude_pattern(&file.path, &patterns)?.clone();
+            Some(IgnoredFile {
+                file,
+                matched_pattern: pattern,
+            })
+        })
+        .collect())
+}
+
+/// Arrange changed files into a directory tree rooted at an unnamed node.
+/// Directories sort before files, then alphabetically.
+#[tauri::command]
+pub fn get_diff_file_tree(files: Vec<FileEntry>) -> TreeNode {
+    let mut root = TreeNode {
+        name: String::new(),
+        path: String::new(),
+        is_dir: true,
+        children: Vec::new(),
+        entry: None,
+    };
+
+    for file in files {
+        let components: Vec<&str> = file.path.split('/').filter(|c| !c.is_empty()).collect();
+        let Some((file_name, dirs)) = components.split_last() else {
+            continue;
+        };
+
+        let mut node = &mut root;
+        for dir in dirs {
+            let dir_path = if node.path.is_empty() {
+                dir.to_string()
+            } else {
+                format!("{}/{}", node.path, dir)
+            };
+            let index = match node
+                .children
+                .iter()
+                .position(|child| child.is_dir && child.name == *dir)
+            {
+                Some(index) => index,
+                None => {
+                    node.children.push(TreeNode {
+                        name: dir.to_string(),
+                        path: dir_path,
+                        is_dir: true,
+                        children: Vec::new(),
+                        entry: None,
+                    });
+                    node.children.len() - 1
+                }
+            };
+            node = &mut node.children[index];
+        }
+
+        node.children.push(TreeNode {
+            name: file_name.to_string(),
+            path: file.path.clone(),
+            is_dir: false,
+            children: Vec::new(),
+            entry: Some(file),
+        });
+    }
+
+    sort_tree(&mut root);
+    root
+}
+
+fn sort_tree(node: &mut TreeNode) {
+    node.children
+        .sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+    for child in &mut node.children {
+        sort_tree(child);
+    }
+}
+
+/// (id, text, category) checklist entries per file kind
+const RUST_CHECKLIST: &[(&str, &str, &str)] = &[
+    ("rust-error-handling", "Check error handling", "correctness"),
+    (
+        "rust-unwrap",
+        "Check for unwrap() and expect()",
+        "correctness",
+    ),
+    ("rust-unsafe", "Check unsafe blocks", "safety"),
+];
+const SQL_CHECKLIST: &[(&str, &str, &str)] = &[
+    ("sql-n-plus-one", "Check for N+1 queries", "performance"),
+    ("sql-index-usage", "Check index usage", "performance"),
+    (
+        "sql-migration-reversible",
+        "Check the migration can be rolled back",
+        "compatibility",
+    ),
+];
+const CONFIG_CHECKLIST: &[(&str, &str, &str)] = &[
+    (
+        "config-backward-compat",
+        "Verify backward compatibility",
+        "compatibility",
+    ),
+    (
+        "config-secrets",
+        "Check no secrets are committed",
+        "security",
+    ),
+];
+const ADDED_CHECKLIST: &[(&str, &str, &str)] = &[(
+    "added-tests",
+    "Check the new file is covered by tests",
+    "testing",
+)];
+const DELETED_CHECKLIST: &[(&str, &str, &str)] = &[(
+    "deleted-references",
+    "Check nothing still references this file",
+    "correctness",
+)];
+
+/// Build a deterministic review checklist from a file's type and change status
+#[tauri::command]
+pub fn get_file_review_checklist(file_path: String, status: String) -> Vec<ChecklistItem> {
+    let extension = Path::new(&file_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    // Type-specific checks don't apply to a file that's being removed
+    let by_type: &[(&str, &str, &str)] = if status == "deleted" {
+        &[]
+    } else if extension == "sql" {
+        SQL_CHECKLIST
+    } else {
+        match detect_language_from_path(&file_path).as_str() {
+            "rust" => RUST_CHECKLIST,
+            "json" | "toml" | "yaml" | "env" => CONFIG_CHECKLIST,
+            _ => &[],
+        }
+    };
+    let by_status: &[(&str, &str, &str)] = match status.as_str() {
+        "added" => ADDED_CHECKLIST,
+        "deleted" => DELETED_CHECKLIST,
+        _ => &[],
     };
 
-    // Write manifest to .revi/sessions/
-    write_manifest(repo_root, &session_id, &manifest)?;
+    by_type
+        .iter()
+        .chain(by_status)
+        .map(|(id, text, category)| ChecklistItem {
+            id: id.to_string(),
+            text: text.to_string(),
+            category: category.to_string(),
+        })
+        .collect()
+}
 
-    Ok(manifest)
+/// Upper bound (inclusive) and label of each change-size bucket; the last is unbounded
+const DENSITY_BUCKETS: &[(u32, &str)] = &[
+    (10, "0-10"),
+    (50, "11-50"),
+    (200, "51-200"),
+    (500, "201-500"),
+    (u32::MAX, "500+"),
+];
+
+/// Bucket files by changed lines (additions + deletions). All buckets are
+/// returned, including empty ones, so the histogram has a stable shape.
+#[tauri::command]
+pub fn get_change_density(files: Vec<FileEntry>) -> Vec<DensityBucket> {
+    let mut buckets: Vec<DensityBucket> = DENSITY_BUCKETS
+        .iter()
+        .map(|(_, label)| DensityBucket {
+            label: label.to_string(),
+            file_count: 0,
+            total_changes: 0,
+        })
+        .collect();
+
+    for file in &files {
+        let changes = file.additions + file.deletions;
+        let index = DENSITY_BUCKETS
+            .iter()
+            .position(|(max, _)| changes <= *max)
+            .unwrap_or(DENSITY_BUCKETS.len() - 1);
+        buckets[index].file_count += 1;
+        buckets[index].total_changes += changes;
+    }
+
+    buckets
+}
+
+/// Group changed files by top-level directory, largest total change first
+#[tauri::command]
+pub fn get_directory_change_summary(files: Vec<FileEntry>) -> Vec<DirectorySummary> {
+    let mut by_dir: HashMap<String, DirectorySummary> = HashMap::new();
+
+    for file in &files {
+        let directory = match file.path.split_once('/') {
+            Some((dir, _)) => dir.to_string(),
+            None => ".".to_string(),
+        };
+        let summary = by_dir
+            .entry(directory.clone())
+            .or_insert_with(|| DirectorySummary {
+                directory,
+                file_count: 0,
+                additions: 0,
+                deletions: 0,
+            });
+        summary.file_count += 1;
+        summary.additions += file.additions;
+        summary.deletions += file.deletions;
+    }
+
+    let mut summaries: Vec<DirectorySummary> = by_dir.into_values().collect();
+    summaries.sort_by(|a, b| {
+        (b.additions + b.deletions)
+            .cmp(&(a.additions + a.deletions))
+            .then_with(|| a.directory.cmp(&b.directory))
+    });
+    summaries
+}
+
+/// Changed-line count at or below which a renamed file is always expanded
+const SMALL_RENAME_LINES: u32 = 5;
+
+/// Decide which files to expand by default. Small renames are always expanded
+/// and binary files never are; the rest are expanded smallest first while their
+/// changed lines fit in what remains of `max_total_lines`. Results keep input order.
+#[tauri::command]
+pub fn get_auto_expand_policy(
+    files: Vec<FileEntry>,
+    max_total_lines: u32,
+) -> Vec<FileExpandPolicy> {
+    let lines = |file: &FileEntry| file.additions + file.deletions;
+
+    let mut policies: Vec<FileExpandPolicy> = files
+        .iter()
+        .map(|file| {
+            let small_rename = file.status == "renamed" && lines(file) <= SMALL_RENAME_LINES;
+            FileExpandPolicy {
+                path: file.path.clone(),
+                expand_all_hunks: small_rename && !file.binary,
+                auto_viewed: small_rename && !file.binary && lines(file) == 0,
+            }
+        })
+        .collect();
+
+    let mut budget = max_total_lines.saturating_sub(
+        files
+            .iter()
+            .zip(&policies)
+            .filter(|(_, policy)| policy.expand_all_hunks)
+            .map(|(file, _)| lines(file))
+            .sum(),
+    );
+
+    let mut remaining: Vec<usize> = (0..files.len())
+        .filter(|&i| !files[i].binary && !policies[i].expand_all_hunks)
+        .collect();
+    remaining.sort_by_key(|&i| lines(&files[i]));
+
+    for i in remaining {
+        let needed = lines(&files[i]);
+        if needed > budget {
+            break;
+        }
+        budget -= needed;
+        policies[i].expand_all_hunks = true;
+    }
+
+    policies
+}
+
+/// Explain why an untracked file is hidden from the uncommitted file list
+#[tauri::command]
+pub fn get_gitignore_patterns(
+    repo_root: String,
+    file_path: String,
+) -> Result<GitignoreInfo, String> {
+    // -z keeps fields unambiguous when the source path contains ':' (Windows
+    // drive letters); it requires --stdin
+    let mut child = Command::new("git")
+        .args(["check-ignore", "-v", "-z", "--stdin"])
+        .current_dir(&repo_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to execute git check-ignore: {}", e))?;
+
+    // Dropping stdin after the write closes it so check-ignore sees EOF
+    child
+        .stdin
+        .take()
+        .ok_or("Failed to open git check-ignore stdin")?
+        .write_all(format!("{}\0", file_path).as_bytes())
+        .map_err(|e| format!("Failed to write to git check-ignore: {}", e))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to read git check-ignore output: {}", e))?;
+
+    // Exit code 1 means the path is not ignored; anything else non-zero is an error
+    if !output.status.success() && output.status.code() != Some(1) {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git check-ignore failed: {}", stderr.trim()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_check_ignore(&stdout))
+}
+
+/// Parse `git check-ignore -v -z` output: `<source>\0<line_num>\0<pattern>\0<path>\0`.
+/// Output is empty when no pattern matches.
+fn parse_check_ignore(stdout: &str) -> GitignoreInfo {
+    let mut fields = stdout.split('\0');
+    let parsed = match (fields.next(), fields.next(), fields.next()) {
+        (Some(source), Some(line_num), Some(pattern)) if !source.is_empty() => {
+            Some((format!("{}:{}", source, line_num), pattern.to_string()))
+        }
+        _ => None,
+    };
+
+    match parsed {
+        Some((source_file, pattern)) => GitignoreInfo {
+            // A matching `!pattern` re-includes the path
+            is_ignored: !pattern.starts_with('!'),
+            matching_pattern: Some(pattern),
+            source_file: Some(source_file),
+        },
+        None => GitignoreInfo {
+            is_ignored: false,
+            matching_pattern: None,
+            source_file: None,
+        },
+    }
 }
 
 /// Detect the default base branch (main, master, or fallback)
@@ -531,6 +2316,19 @@ fn parse_rename_path(path: &str) -> (String, Option<String>) {
     (path.to_string(), None)
 }
 
+/// Build a HashMap of new path -> similarity score for renames in `git diff --name-status`
+/// output, where renames look like `R087\told\tnew`.
+fn parse_rename_scores(output: &str) -> HashMap<String, u8> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split('\t').collect();
+            let score = parts.first()?.strip_prefix('R')?.parse().ok()?;
+            Some((parts.last()?.to_string(), score))
+        })
+        .collect()
+}
+
 /// Build a HashMap of path -> status letter from `git diff --name-status` output.
 fn parse_name_status(output: &str) -> std::collections::HashMap<String, String> {
     let mut map = std::collections::HashMap::new();
@@ -576,20 +2374,23 @@ fn is_binary_file(path: &Path) -> bool {
 
 /// Get list of uncommitted files (staged + unstaged + untracked)
 fn get_uncommitted_files(repo_root: &str) -> Result<Vec<FileEntry>, String> {
+    let find_renames = find_renames_arg(read_rename_threshold(repo_root));
+
     // Get diff stats for tracked files (both staged and unstaged) against HEAD
     let diff_output = Command::new("git")
-        .args(["diff", "HEAD", "--numstat", "--find-renames"])
+        .args(["diff", "HEAD", "--numstat", &find_renames])
         .current_dir(repo_root)
         .output()
         .map_err(|e| format!("Failed to get diff: {}", e))?;
 
     // Get name-status for accurate status detection
     let name_status_output = Command::new("git")
-        .args(["diff", "HEAD", "--name-status", "--find-renames"])
+        .args(["diff", "HEAD", "--name-status", &find_renames])
         .current_dir(repo_root)
         .output()
         .map_err(|e| format!("Failed to get name-status: {}", e))?;
     let name_status_map = parse_name_status(&String::from_utf8_lossy(&name_status_output.stdout));
+    let rename_scores = parse_rename_scores(&String::from_utf8_lossy(&name_status_output.stdout));
 
     let mut files = Vec::new();
     let stdout = String::from_utf8_lossy(&diff_output.stdout);
@@ -622,6 +2423,7 @@ fn get_uncommitted_files(repo_root: &str) -> Result<Vec<FileEntry>, String> {
                 .unwrap_or_else(|| "modified".to_string())
         };
 
+        let rename_score = rename_scores.get(&path).copied();
         files.push(FileEntry {
             path,
             status,
@@ -629,6 +2431,9 @@ fn get_uncommitted_files(repo_root: &str) -> Result<Vec<FileEntry>, String> {
             deletions,
             renamed_from,
             binary,
+            locally_absent: false,
+            conflict_side: None,
+            rename_score,
         });
     }
 
@@ -661,6 +2466,87 @@ fn get_uncommitted_files(repo_root: &str) -> Result<Vec<FileEntry>, String> {
             deletions: 0,
             renamed_from: None,
             binary,
+            locally_absent: false,
+            conflict_side: None,
+            rename_score: None,
+        });
+    }
+
+    Ok(files)
+}
+
+/// Whether a merge is in progress, i.e. conflict mode is available
+#[tauri::command]
+pub fn get_merge_in_progress(repo_root: String) -> bool {
+    is_merge_in_progress(&repo_root)
+}
+
+/// Check for `MERGE_HEAD` in the git dir (resolved via `--git-path` so worktrees work)
+fn is_merge_in_progress(repo_root: &str) -> bool {
+    let output = match Command::new("git")
+        .args(["rev-parse", "--git-path", "MERGE_HEAD"])
+        .current_dir(repo_root)
+        .output()
+    {
+        Ok(output) if output.status.success() => output,
+        _ => return false,
+    };
+
+    let merge_head = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Path::new(repo_root).join(merge_head).exists()
+}
+
+/// Files involved in an in-progress merge: unmerged (conflicted) paths are
+/// tagged "both", everything else changed on the MERGE_HEAD side is "theirs"
+fn get_merge_files(
+    repo_root: &str,
+    merge_base: &str,
+    merge_head: &str,
+) -> Result<Vec<FileEntry>, String> {
+    let output = Command::new("git")
+        .args(["diff", "--name-only", "--diff-filter=U"])
+        .current_dir(repo_root)
+        .output()
+        .map_err(|e| format!("Failed to list conflicted files: {}", e))?;
+
+    if !output.status.success() {
+        return Err("Failed to list conflicted files".to_string());
+    }
+
+    let conflicted: HashSet<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_string())
+        .collect();
+
+    let mut files = get_changed_files(repo_root, merge_base, merge_head)?;
+    for file in &mut files {
+        let side = if conflicted.contains(&file.path) {
+            "both"
+        } else {
+            "theirs"
+        };
+        file.conflict_side = Some(side.to_string());
+    }
+
+    // Conflicts can also come from paths theirs didn't touch under the same name
+    // (e.g. rename/delete); list them so nothing unresolved is hidden
+    let mut missing: Vec<&String> = conflicted
+        .iter()
+        .filter(|path| !files.iter().any(|f| &f.path == *path))
+        .collect();
+    missing.sort();
+    for path in missing {
+        files.push(FileEntry {
+            path: path.clone(),
+            status: "modified".to_string(),
+            additions: 0,
+            deletions: 0,
+            renamed_from: None,
+            binary: false,
+            locally_absent: false,
+            conflict_side: Some("both".to_string()),
+            rename_score: None,
         });
     }
 
@@ -681,6 +2567,50 @@ fn get_merge_base(repo_root: &str, branch: &str) -> Result<String, String> {
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
+/// Check whether `candidate_sha` is an ancestor of (or equal to) `from_ref`
+#[tauri::command]
+pub fn is_ref_reachable(
+    repo_root: String,
+    candidate_sha: String,
+    from_ref: String,
+) -> Result<bool, String> {
+    let output = Command::new("git")
+        .args(["merge-base", "--is-ancestor", &candidate_sha, &from_ref])
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to check ancestry: {}", e))?;
+
+    // Exit code 0 = ancestor, 1 = not an ancestor, anything else = error (e.g. unknown ref)
+    match output.status.code() {
+        Some(0) => Ok(true),
+        Some(1) => Ok(false),
+        _ => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(format!("git merge-base failed: {}", stderr.trim()))
+        }
+    }
+}
+
+/// Find the best common ancestor of two commits
+#[tauri::command]
+pub fn get_common_ancestor(
+    repo_root: String,
+    sha_a: String,
+    sha_b: String,
+) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(["merge-base", &sha_a, &sha_b])
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to get merge-base: {}", e))?;
+
+    if !output.status.success() {
+        return Err("No merge-base found".to_string());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
 fn get_ref_info(repo_root: &str, ref_name: &str) -> Result<RefInfo, String> {
     let output = Command::new("git")
         .args(["rev-parse", ref_name])
@@ -700,15 +2630,60 @@ fn get_ref_info(repo_root: &str, ref_name: &str) -> Result<RefInfo, String> {
     })
 }
 
+/// Changed files for a base/head pair, including the "INDEX" and
+/// "WORKING_TREE" pseudo-refs
+pub(crate) fn get_files_for_range(
+    repo_root: &str,
+    base_sha: &str,
+    head_sha: &str,
+) -> Result<Vec<FileEntry>, String> {
+    match (base_sha, head_sha) {
+        ("INDEX", "WORKING_TREE") => get_uncommitted_unstaged_files(repo_root.to_string()),
+        (_, "WORKING_TREE") => get_uncommitted_files(repo_root),
+        (_, "INDEX") => get_uncommitted_staged_files(repo_root.to_string()),
+        _ => get_changed_files(repo_root, base_sha, head_sha),
+    }
+}
+
 fn get_changed_files(
     repo_root: &str,
     base_sha: &str,
     head_sha: &str,
 ) -> Result<Vec<FileEntry>, String> {
     let diff_range = format!("{}...{}", base_sha, head_sha);
+    get_diff_files(repo_root, &[&diff_range])
+}
+
+/// List the files changed in a stash entry (e.g. `stash@{0}`) relative to the
+/// commit it was made on, without creating a session. Like `git stash show`,
+/// this covers tracked changes only.
+#[tauri::command]
+pub fn get_stash_diff(repo_root: String, stash_ref: String) -> Result<Vec<FileEntry>, String> {
+    let stash = get_ref_info(&repo_root, &stash_ref)?;
+    let parent = format!("{}^1", stash.sha);
+    get_diff_files(&repo_root, &[&parent, &stash.sha])
+}
 
+/// Staged changes only: HEAD vs the index
+#[tauri::command]
+pub fn get_uncommitted_staged_files(repo_root: String) -> Result<Vec<FileEntry>, String> {
+    get_diff_files(&repo_root, &["--cached"])
+}
+
+/// Unstaged changes only: the index vs the working tree
+#[tauri::command]
+pub fn get_uncommitted_unstaged_files(repo_root: String) -> Result<Vec<FileEntry>, String> {
+    get_diff_files(&repo_root, &[])
+}
+
+/// Run `git diff --numstat --find-renames <diff_args>` and build file entries,
+/// using `--name-status` for accurate status detection. Renames are detected
+/// at the configured `renameThreshold`.
+fn get_diff_files(repo_root: &str, diff_args: &[&str]) -> Result<Vec<FileEntry>, String> {
+    let find_renames = find_renames_arg(read_rename_threshold(repo_root));
     let output = Command::new("git")
-        .args(["diff", "--numstat", "--find-renames", &diff_range])
+        .args(["diff", "--numstat", &find_renames])
+        .args(diff_args)
         .current_dir(repo_root)
         .output()
         .map_err(|e| format!("Failed to get diff: {}", e))?;
@@ -719,11 +2694,13 @@ fn get_changed_files(
 
     // Get name-status for accurate status detection
     let name_status_output = Command::new("git")
-        .args(["diff", "--name-status", "--find-renames", &diff_range])
+        .args(["diff", "--name-status", &find_renames])
+        .args(diff_args)
         .current_dir(repo_root)
         .output()
         .map_err(|e| format!("Failed to get name-status: {}", e))?;
     let name_status_map = parse_name_status(&String::from_utf8_lossy(&name_status_output.stdout));
+    let rename_scores = parse_rename_scores(&String::from_utf8_lossy(&name_status_output.stdout));
 
     let mut files = Vec::new();
     let stdout = String::from_utf8_lossy(&output.stdout);
@@ -742,31 +2719,460 @@ fn get_changed_files(
         let deletions: u32 = parts[1].parse().unwrap_or(0);
         let path_part = parts[2];
 
-        // Check for binary files (- - indicates binary)
-        let binary = parts[0] == "-" && parts[1] == "-";
+        // Check for binary files (- - indicates binary)
+        let binary = parts[0] == "-" && parts[1] == "-";
+
+        // Check for renames using the shared helper
+        let (path, renamed_from) = parse_rename_path(path_part);
+        let status = if renamed_from.is_some() {
+            "renamed".to_string()
+        } else {
+            name_status_map
+                .get(&path)
+                .cloned()
+                .unwrap_or_else(|| "modified".to_string())
+        };
+
+        let rename_score = rename_scores.get(&path).copied();
+        files.push(FileEntry {
+            path,
+            status,
+            additions,
+            deletions,
+            renamed_from,
+            binary,
+            locally_absent: false,
+            conflict_side: None,
+            rename_score,
+        });
+    }
+
+    Ok(files)
+}
+
+/// Path of a session's manifest in .revi/sessions/
+fn manifest_path(repo_root: &str, session_id: &str) -> Result<PathBuf, String> {
+    if session_id.is_empty() || session_id.contains(['/', '\\', '.']) {
+        return Err(format!("Invalid session id: {}", session_id));
+    }
+
+    Ok(Path::new(repo_root)
+        .join(".revi")
+        .join("sessions")
+        .join(format!("{}.json", session_id)))
+}
+
+/// Load a session manifest by id
+fn read_manifest(repo_root: &str, session_id: &str) -> Result<ReviewManifest, String> {
+    let path = manifest_path(repo_root, session_id)?;
+    load_session(path.to_string_lossy().into_owned())
+}
+
+/// Create a new session over the same repository with a different comparison mode.
+/// The name ("Copy of ...") and tags carry over; review state does not.
+#[tauri::command]
+pub fn duplicate_session(
+    repo_root: String,
+    source_session_id: String,
+    new_mode: ComparisonMode,
+) -> Result<ReviewManifest, String> {
+    let source = read_manifest(&repo_root, &source_session_id)?;
+    let current_branch = get_current_branch(&repo_root);
+
+    let mut manifest = create_session_with_mode(&repo_root, new_mode, current_branch, None)?;
+    manifest.name = source.name.map(|name| format!("Copy of {}", name));
+    manifest.tags = source.tags;
+    write_manifest(&repo_root, &manifest.session_id, &manifest)?;
+
+    Ok(manifest)
+}
+
+/// Thresholds for `get_session_age_warning`: (commits since session, age in hours)
+const WARN_AGE_THRESHOLDS: (u32, f64) = (10, 72.0);
+const STALE_AGE_THRESHOLDS: (u32, f64) = (50, 168.0);
+
+/// Check whether HEAD has moved on since a session was created.
+/// Returns None when the session has no commit to compare against (index vs working tree).
+#[tauri::command]
+pub fn get_session_age_warning(
+    repo_root: String,
+    session_id: String,
+) -> Result<Option<SessionAgeWarning>, String> {
+    let manifest = read_manifest(&repo_root, &session_id)?;
+
+    // Uncommitted sessions record HEAD as their base
+    let stored_sha = [&manifest.head.sha, &manifest.base.sha]
+        .into_iter()
+        .find(|sha| *sha != "WORKING_TREE" && *sha != "INDEX");
+    let Some(stored_sha) = stored_sha else {
+        return Ok(None);
+    };
+
+    let head_sha = get_ref_info(&repo_root, "HEAD")?.sha;
+    let head_sha_changed = *stored_sha != head_sha;
+    let commits_since_session = if head_sha_changed {
+        let output = Command::new("git")
+            .args([
+                "rev-list",
+                "--count",
+                &format!("{}..{}", stored_sha, head_sha),
+            ])
+            .current_dir(&repo_root)
+            .output()
+            .map_err(|e| format!("Failed to count commits: {}", e))?;
+        String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .unwrap_or(0)
+    } else {
+        0
+    };
+
+    let session_age_hours = chrono::DateTime::parse_from_rfc3339(&manifest.created_at)
+        .map(|created| (Utc::now() - created.with_timezone(&Utc)).num_seconds() as f64 / 3600.0)
+        .unwrap_or(0.0)
+        .max(0.0);
+
+    // Age alone isn't misleading; it only matters once HEAD has moved
+    let warning_level = if !head_sha_changed {
+        "none"
+    } else if commits_since_session >= STALE_AGE_THRESHOLDS.0
+        || session_age_hours >= STALE_AGE_THRESHOLDS.1
+    {
+        "stale"
+    } else if commits_since_session >= WARN_AGE_THRESHOLDS.0
+        || session_age_hours >= WARN_AGE_THRESHOLDS.1
+    {
+        "warn"
+    } else {
+        "info"
+    };
+
+    Ok(Some(SessionAgeWarning {
+        session_age_hours,
+        commits_since_session,
+        head_sha_changed,
+        warning_level: warning_level.to_string(),
+    }))
+}
+
+/// Recompute a session's base against the current refs (e.g. after `git fetch`
+/// moved `origin/main`), so the frontend can offer to regenerate the session
+#[tauri::command]
+pub fn get_merge_base_for_session(
+    repo_root: String,
+    session_id: String,
+) -> Result<SessionMergeBase, String> {
+    let manifest = read_manifest(&repo_root, &session_id)?;
+
+    let base = match &manifest.comparison_mode {
+        Some(ComparisonMode::Branch { base_branch }) => {
+            match get_merge_base(&repo_root, base_branch) {
+                Ok(sha) => RefInfo {
+                    ref_name: base_branch.clone(),
+                    sha,
+                },
+                Err(_) => get_ref_info(&repo_root, base_branch)?,
+            }
+        }
+        Some(ComparisonMode::Custom { base_ref, .. }) => get_ref_info(&repo_root, base_ref)?,
+        Some(ComparisonMode::Uncommitted) | Some(ComparisonMode::Staged) => {
+            get_ref_info(&repo_root, "HEAD")?
+        }
+        Some(ComparisonMode::Conflict) => RefInfo {
+            ref_name: "merge-base".to_string(),
+            sha: get_merge_base(&repo_root, "MERGE_HEAD")?,
+        },
+        // The index and sessions without a recorded mode have nothing to recompute
+        Some(ComparisonMode::Unstaged) | None => RefInfo {
+            ref_name: manifest.base.ref_name.clone(),
+            sha: manifest.base.sha.clone(),
+        },
+    };
+
+    let base_sha_changed = base.sha != manifest.base.sha;
+    Ok(SessionMergeBase {
+        base,
+        base_sha_changed,
+    })
+}
+
+/// Point an existing session at a new base/head pair, keeping its session id.
+/// State saved under the old SHA pair is left on disk; an empty state is created
+/// for the new pair if none exists yet.
+#[tauri::command]
+pub fn set_comparison_range(
+    repo_root: String,
+    session_id: String,
+    new_base_ref: String,
+    new_head_ref: String,
+) -> Result<ReviewManifest, String> {
+    let mut manifest = read_manifest(&repo_root, &session_id)?;
+
+    let base = get_ref_info(&repo_root, &new_base_ref)?;
+    let head = get_ref_info(&repo_root, &new_head_ref)?;
+    manifest.files = without_excluded_files(
+        &repo_root,
+        get_changed_files(&repo_root, &base.sha, &head.sha)?,
+    );
+    manifest.comparison_mode = Some(ComparisonMode::Custom {
+        base_ref: new_base_ref,
+        head_ref: new_head_ref,
+    });
+    manifest.base = base;
+    manifest.head = head;
+    write_manifest(&repo_root, &session_id, &manifest)?;
+
+    if load_review_state(
+        repo_root.clone(),
+        manifest.base.sha.clone(),
+        manifest.head.sha.clone(),
+    )?
+    .is_none()
+    {
+        write_review_state(
+            &repo_root,
+            &PersistedState {
+                version: 1,
+                session_id,
+                base_sha: manifest.base.sha.clone(),
+                head_sha: manifest.head.sha.clone(),
+                files: HashMap::new(),
+                ui: UiState {
+                    mode: "split".to_string(),
+                    sidebar_width: 280,
+                    sidebar_visible: true,
+                },
+                threads: Vec::new(),
+            },
+        )?;
+    }
+
+    Ok(manifest)
+}
+
+/// Export a session for sharing, replacing local paths with the origin remote URL
+#[tauri::command]
+pub fn export_session_portable(
+    repo_root: String,
+    session_id: String,
+) -> Result<PortableSession, String> {
+    let manifest = read_manifest(&repo_root, &session_id)?;
+    let origin_url = get_origin_url(&repo_root);
+
+    Ok(PortableSession {
+        version: manifest.version,
+        session_id: manifest.session_id,
+        origin_url,
+        base: manifest.base,
+        head: manifest.head,
+        branch: manifest.worktree.map(|w| w.branch),
+        files: manifest.files,
+        created_at: manifest.created_at,
+        comparison_mode: manifest.comparison_mode,
+        pr_info: manifest.pr_info,
+        skipped_count: manifest.skipped_count,
+        notes: manifest.notes,
+        status: manifest.status,
+        name: manifest.name,
+        tags: manifest.tags,
+        is_single_commit: manifest.is_single_commit,
+    })
+}
+
+/// Import a shared session into a local clone of the same repository.
+/// The session gets a fresh local id; the exported id is never used as a path.
+#[tauri::command]
+pub async fn import_portable_session(
+    portable: PortableSession,
+    local_repo_path: String,
+) -> Result<ReviewManifest, String> {
+    // Fetching missing commits can take a while; keep it off the async runtime
+    tauri::async_runtime::spawn_blocking(move || {
+        import_portable_session_blocking(portable, &local_repo_path)
+    })
+    .await
+    .map_err(|e| format!("Failed to import session: {}", e))?
+}
+
+fn import_portable_session_blocking(
+    portable: PortableSession,
+    local_repo_path: &str,
+) -> Result<ReviewManifest, String> {
+    let repo_root = get_repo_root(local_repo_path)?;
+
+    if let Some(expected) = &portable.origin_url {
+        let local = get_origin_url(&repo_root);
+        if local.as_deref().map(normalize_remote_url) != Some(normalize_remote_url(expected)) {
+            return Err(format!(
+                "Session was exported from {}, but the local origin is {}",
+                expected,
+                local.as_deref().unwrap_or("not set")
+            ));
+        }
+    }
+
+    // Both SHAs must exist locally for the diffs to load
+    for sha in [&portable.base.sha, &portable.head.sha] {
+        if sha != "WORKING_TREE" && sha != "INDEX" {
+            ensure_commit_available(&repo_root, sha, "origin", sha)?;
+        }
+    }
+
+    let manifest = ReviewManifest {
+        version: portable.version,
+        session_id: nanoid!(12),
+        repo_root: repo_root.clone(),
+        worktree: portable.branch.map(|branch| WorktreeInfo {
+            path: repo_root.clone(),
+            branch,
+        }),
+        base: portable.base,
+        head: portable.head,
+        files: portable.files,
+        created_at: portable.created_at,
+        comparison_mode: portable.comparison_mode,
+        pr_info: portable.pr_info,
+        skipped_count: portable.skipped_count,
+        notes: portable.notes,
+        status: portable.status,
+        name: portable.name,
+        tags: portable.tags,
+        is_single_commit: portable.is_single_commit,
+    };
+    write_manifest(&repo_root, &manifest.session_id, &manifest)?;
+
+    Ok(manifest)
+}
+
+/// URL of the `origin` remote, if the repository has one
+fn get_origin_url(repo_root: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["remote", "get-url", "origin"])
+        .current_dir(repo_root)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!url.is_empty()).then_some(url)
+}
+
+/// Reduce a remote URL to `host/path` so SSH and HTTPS forms of the same
+/// remote compare equal (e.g. `git@github.com:o/r.git` and `https://github.com/o/r`)
+fn normalize_remote_url(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    let url = url.strip_suffix(".git").unwrap_or(url);
+    let rest = match url.split_once("://") {
+        Some((_, rest)) => rest.to_string(),
+        // scp-like syntax: [user@]host:path
+        None => url.replacen(':', "/", 1),
+    };
+    let rest = rest
+        .rsplit_once('@')
+        .map_or(rest.as_str(), |(_, host_path)| host_path);
+    rest.to_lowercase()
+}
+
+/// Read a git config value (e.g. `user.name`, `branch.main.merge`) from the
+/// "local", "global", or "system" scope. Returns None if the key isn't set.
+#[tauri::command]
+pub fn get_git_config(
+    repo_root: String,
+    key: String,
+    scope: String,
+) -> Result<Option<String>, String> {
+    let scope_flag = match scope.as_str() {
+        "local" => "--local",
+        "global" => "--global",
+        "system" => "--system",
+        _ => return Err(format!("Invalid config scope: {}", scope)),
+    };
+    if !is_valid_config_key(&key) {
+        return Err(format!("Invalid config key: {}", key));
+    }
+
+    let output = Command::new("git")
+        .args(["config", scope_flag, "--get", &key])
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to read git config: {}", e))?;
+
+    // Exit code 1 means the key isn't set in this scope
+    if output.status.code() == Some(1) {
+        return Ok(None);
+    }
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git config failed: {}", stderr.trim()));
+    }
+
+    Ok(Some(
+        String::from_utf8_lossy(&output.stdout)
+            .trim_end_matches('\n')
+            .to_string(),
+    ))
+}
+
+/// `section.key` or `section.subsection.key`: a lowercase section name, then
+/// dot-separated names. Keeps keys from being read as `git config` options.
+fn is_valid_config_key(key: &str) -> bool {
+    let Some((section, rest)) = key.split_once('.') else {
+        return false;
+    };
+
+    section.starts_with(|c: char| c.is_ascii_lowercase())
+        && section
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !rest.is_empty()
+        && !rest.ends_with('.')
+        && rest
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '/'))
+}
 
-        // Check for renames using the shared helper
-        let (path, renamed_from) = parse_rename_path(path_part);
-        let status = if renamed_from.is_some() {
-            "renamed".to_string()
-        } else {
-            name_status_map
-                .get(&path)
-                .cloned()
-                .unwrap_or_else(|| "modified".to_string())
-        };
+/// Save free-form review notes on a session
+#[tauri::command]
+pub fn save_session_notes(
+    repo_root: String,
+    session_id: String,
+    notes: String,
+) -> Result<(), String> {
+    let mut manifest = read_manifest(&repo_root, &session_id)?;
+    manifest.notes = if notes.trim().is_empty() {
+        None
+    } else {
+        Some(notes)
+    };
+    write_manifest(&repo_root, &session_id, &manifest)
+}
 
-        files.push(FileEntry {
-            path,
-            status,
-            additions,
-            deletions,
-            renamed_from,
-            binary,
-        });
-    }
+/// Get a session's review notes, if any
+#[tauri::command]
+pub fn get_session_notes(repo_root: String, session_id: String) -> Result<Option<String>, String> {
+    Ok(read_manifest(&repo_root, &session_id)?.notes)
+}
 
-    Ok(files)
+/// Set the review status of a session
+#[tauri::command]
+pub fn set_review_status(
+    repo_root: String,
+    session_id: String,
+    status: ReviewStatus,
+) -> Result<(), String> {
+    let mut manifest = read_manifest(&repo_root, &session_id)?;
+    manifest.status = status;
+    write_manifest(&repo_root, &session_id, &manifest)
+}
+
+/// Get the review status of a session
+#[tauri::command]
+pub fn get_review_status(repo_root: String, session_id: String) -> Result<ReviewStatus, String> {
+    Ok(read_manifest(&repo_root, &session_id)?.status)
 }
 
 fn write_manifest(
@@ -959,45 +3365,790 @@ pub fn list_branches(repo_root: String) -> Result<Vec<String>, String> {
 /// List recent commits in the repository
 #[tauri::command]
 pub fn list_recent_commits(repo_root: String, count: u32) -> Result<Vec<CommitInfo>, String> {
+    let output = Command::new("git")
+        .args(["log", &format!("-{}", count), COMMIT_LOG_FORMAT])
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to list commits: {}", e))?;
+
+    if !output.status.success() {
+        return Err("Failed to get commit history".to_string());
+    }
+
+    Ok(parse_commit_log(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// List commits reachable from `head_sha` but not from `base_sha`, newest first
+#[tauri::command]
+pub fn list_commits_between(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    max_count: u32,
+) -> Result<Vec<CommitInfo>, String> {
     let output = Command::new("git")
         .args([
             "log",
-            &format!("-{}", count),
-            "--format=%H%n%h%n%s%n%an%n%aI%n---",
+            &format!("{}..{}", base_sha, head_sha),
+            "-n",
+            &max_count.to_string(),
+            COMMIT_LOG_FORMAT,
         ])
         .current_dir(&repo_root)
         .output()
         .map_err(|e| format!("Failed to list commits: {}", e))?;
 
     if !output.status.success() {
-        return Err("Failed to get commit history".to_string());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git log failed: {}", stderr.trim()));
+    }
+
+    Ok(parse_commit_log(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// List commits on `head_sha` whose changes aren't already in `base_sha`.
+/// Unlike `list_commits_between`, commits already applied to the base under a
+/// different SHA (matched by patch) are filtered out.
+#[tauri::command]
+pub fn get_cherry_pickable_commits(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+) -> Result<Vec<CommitInfo>, String> {
+    let output = Command::new("git")
+        .args([
+            "log",
+            "--cherry-pick",
+            "--right-only",
+            &format!("{}...{}", base_sha, head_sha),
+            COMMIT_LOG_FORMAT,
+        ])
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to list commits: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git log failed: {}", stderr.trim()));
+    }
+
+    Ok(parse_commit_log(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Map each file to the commits in `base_sha..head_sha` that touched it,
+/// newest first. Files untouched in the range map to an empty list.
+/// One `git log` runs per file, in parallel.
+#[tauri::command]
+pub fn get_commits_for_session_files(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    file_paths: Vec<String>,
+) -> Result<HashMap<String, Vec<CommitInfo>>, String> {
+    // Uncommitted changes have no commits of their own; use the commits up to HEAD
+    let head = if head_sha == "WORKING_TREE" || head_sha == "INDEX" {
+        "HEAD"
+    } else {
+        head_sha.as_str()
+    };
+    let range = format!("{}..{}", base_sha, head);
+
+    file_paths
+        .into_par_iter()
+        .map(|path| {
+            let output = Command::new("git")
+                .args(["log", &range, COMMIT_LOG_FORMAT, "--", &path])
+                .current_dir(&repo_root)
+                .output()
+                .map_err(|e| format!("Failed to list commits for {}: {}", path, e))?;
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(format!("git log failed for {}: {}", path, stderr.trim()));
+            }
+
+            let commits = parse_commit_log(&String::from_utf8_lossy(&output.stdout));
+            Ok((path, commits))
+        })
+        .collect()
+}
+
+/// Session files touched by commits from `author_email` in `base_sha..head_sha`.
+/// Additions/deletions are re-aggregated over that author's commits only, so
+/// a co-authored branch can be reviewed one author at a time.
+#[tauri::command]
+pub fn get_files_changed_by_author(
+    repo_root: String,
+    base_sha: String,
+    head_sha: String,
+    author_email: String,
+) -> Result<Vec<FileEntry>, String> {
+    let session_files = get_files_for_range(&repo_root, &base_sha, &head_sha)?;
+
+    // Uncommitted changes have no author; use the commits up to HEAD
+    let head = if head_sha == "WORKING_TREE" || head_sha == "INDEX" {
+        "HEAD"
+    } else {
+        head_sha.as_str()
+    };
+    let output = Command::new("git")
+        .args([
+            "log",
+            "--fixed-strings",
+            &format!("--author={}", author_email),
+            "--numstat",
+            "--no-renames",
+            "--format=",
+            &format!("{}..{}", base_sha, head),
+        ])
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to list commits by author: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git log failed: {}", stderr.trim()));
+    }
+
+    // Each line: "<additions>\t<deletions>\t<path>" ("-" counts for binary files)
+    let mut totals: HashMap<String, (u32, u32)> = HashMap::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let mut parts = line.splitn(3, '\t');
+        let (Some(additions), Some(deletions), Some(path)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let entry = totals.entry(path.to_string()).or_default();
+        entry.0 += additions.parse::<u32>().unwrap_or(0);
+        entry.1 += deletions.parse::<u32>().unwrap_or(0);
+    }
+
+    Ok(session_files
+        .into_iter()
+        .filter_map(|mut file| {
+            let (additions, deletions) = *totals.get(&file.path)?;
+            file.additions = additions;
+            file.deletions = deletions;
+            Some(file)
+        })
+        .collect())
+}
+
+/// Parse `git log` output in `COMMIT_LOG_FORMAT`
+fn parse_commit_log(stdout: &str) -> Vec<CommitInfo> {
+    let mut commits = Vec::new();
+
+    // Each record: 6 header lines, then the body up to the separator
+    for record in stdout.split('\x1e') {
+        let mut lines = record.trim_start_matches('\n').splitn(7, '\n');
+        let (Some(sha), Some(short_sha), Some(message), Some(author), Some(date), Some(parents)) = (
+            lines.next(),
+            lines.next(),
+            lines.next(),
+            lines.next(),
+            lines.next(),
+            lines.next(),
+        ) else {
+            continue;
+        };
+
+        let body = lines.next().unwrap_or("").trim();
+        let body_preview =
+            (!body.is_empty()).then(|| body.chars().take(BODY_PREVIEW_CHARS).collect());
+
+        commits.push(CommitInfo {
+            sha: sha.trim().to_string(),
+            short_sha: short_sha.trim().to_string(),
+            message: message.trim().to_string(),
+            author: author.trim().to_string(),
+            date: date.trim().to_string(),
+            parent_count: parents.split_whitespace().count() as u32,
+            body_preview,
+        });
+    }
+
+    commits
+}
+
+/// Get a commit's full message, split into subject, body, and trailers
+/// (`Key: value` lines in the message's final paragraph, removed from the body)
+#[tauri::command]
+pub fn get_commit_body(repo_root: String, sha: String) -> Result<CommitBody, String> {
+    let output = Command::new("git")
+        .args(["show", "-s", "--format=%B", &sha])
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to execute git show: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git show failed: {}", stderr.trim()));
+    }
+
+    Ok(parse_commit_body(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_commit_body(message: &str) -> CommitBody {
+    let message = message.trim();
+    let (subject, body) = match message.split_once("\n\n") {
+        Some((subject, body)) => (subject, body.trim()),
+        None => (message, ""),
+    };
+
+    // Trailers only count if the whole final paragraph is trailers, as with `git interpret-trailers`
+    let (rest, last_paragraph) = body.rsplit_once("\n\n").unwrap_or(("", body));
+    let parsed: Option<Vec<(&str, &str)>> = last_paragraph
+        .lines()
+        .map(|line| {
+            let (key, value) = line.split_once(": ")?;
+            let is_key =
+                !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+            (is_key && !value.trim().is_empty()).then_some((key, value.trim()))
+        })
+        .collect();
+
+    let mut trailers: HashMap<String, Vec<String>> = HashMap::new();
+    let body = match parsed.filter(|parsed| !parsed.is_empty()) {
+        Some(parsed) => {
+            for (key, value) in parsed {
+                trailers
+                    .entry(key.to_string())
+                    .or_default()
+                    .push(value.to_string());
+            }
+            rest.trim_end()
+        }
+        None => body,
+    };
+
+    CommitBody {
+        subject: subject.replace('\n', " "),
+        body: body.to_string(),
+        trailers,
+    }
+}
+
+/// Get commit graph data (parents and decorations) for the most recent commits
+#[tauri::command]
+pub fn get_branch_graph(repo_root: String, max_commits: u32) -> Result<Vec<GraphCommit>, String> {
+    let output = Command::new("git")
+        .args([
+            "log",
+            "-n",
+            &max_commits.to_string(),
+            "--format=%H%n%P%n%D%n%s%n%an%n%aI%n---",
+        ])
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to get commit graph: {}", e))?;
+
+    if !output.status.success() {
+        return Err("Failed to get commit graph".to_string());
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let mut commits = Vec::new();
 
-    // Parse commits - each commit is 5 lines followed by "---"
+    // Parse commits - each commit is 6 lines followed by "---"
+    // (%P and %D may be empty lines, so lines are consumed positionally)
     let lines: Vec<&str> = stdout.lines().collect();
     let mut i = 0;
 
-    while i + 4 < lines.len() {
+    while i + 5 < lines.len() {
         let sha = lines[i].trim().to_string();
-        let short_sha = lines[i + 1].trim().to_string();
-        let message = lines[i + 2].trim().to_string();
-        let author = lines[i + 3].trim().to_string();
-        let date = lines[i + 4].trim().to_string();
-
-        commits.push(CommitInfo {
+        let parents = lines[i + 1]
+            .split_whitespace()
+            .map(|p| p.to_string())
+            .collect();
+        let refs = parse_decorations(lines[i + 2]);
+        let message = lines[i + 3].trim().to_string();
+        let author = lines[i + 4].trim().to_string();
+        let date = lines[i + 5].trim().to_string();
+
+        commits.push(GraphCommit {
+            short_sha: sha.chars().take(7).collect(),
             sha,
-            short_sha,
             message,
+            parents,
+            refs,
             author,
             date,
         });
 
-        // Skip to next commit (5 data lines + 1 separator)
-        i += 6;
+        // Skip to next commit (6 data lines + 1 separator)
+        i += 7;
     }
 
     Ok(commits)
 }
+
+/// Parse a `%D` decoration line (e.g. `HEAD -> main, origin/main, tag: v1.0`)
+/// into plain branch/tag names
+fn parse_decorations(line: &str) -> Vec<String> {
+    line.split(", ")
+        .map(|r| r.trim())
+        .filter(|r| !r.is_empty() && *r != "HEAD")
+        .map(|r| {
+            let r = r.strip_prefix("HEAD -> ").unwrap_or(r);
+            r.strip_prefix("tag: ").unwrap_or(r).to_string()
+        })
+        .collect()
+}
+
+/// List all tags in version-aware order
+#[tauri::command]
+pub fn list_tags(repo_root: String) -> Result<Vec<TagInfo>, String> {
+    let output = Command::new("git")
+        .args([
+            "tag",
+            "-l",
+            "--sort=version:refname",
+            "--format=%(refname:short)%09%(objecttype)%09%(objectname)%09%(*objectname)%09%(taggername)%09%(taggerdate:iso)%09%(contents:subject)",
+        ])
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to list tags: {}", e))?;
+
+    if !output.status.success() {
+        return Err("Failed to list tags".to_string());
+    }
+
+    let non_empty = |value: &str| {
+        let value = value.trim();
+        if value.is_empty() {
+            None
+        } else {
+            Some(value.to_string())
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut tags = Vec::new();
+
+    for line in stdout.lines() {
+        let parts: Vec<&str> = line.splitn(7, '\t').collect();
+        if parts.len() < 4 {
+            continue;
+        }
+
+        let sha = parts[2].to_string();
+        let is_annotated = parts[1] == "tag";
+        let target_sha = if is_annotated {
+            non_empty(parts[3]).unwrap_or_else(|| sha.clone())
+        } else {
+            sha.clone()
+        };
+
+        tags.push(TagInfo {
+            name: parts[0].to_string(),
+            sha,
+            target_sha,
+            is_annotated,
+            // Lightweight tags have no tagger; their subject is the commit's, not a tag message
+            tagger: parts.get(4).and_then(|v| non_empty(v)),
+            date: parts.get(5).and_then(|v| non_empty(v)),
+            message: if is_annotated {
+                parts.get(6).and_then(|v| non_empty(v))
+            } else {
+                None
+            },
+        });
+    }
+
+    Ok(tags)
+}
+
+/// Check whether a commit is GPG-signed and whether the signature verifies
+#[tauri::command]
+pub fn get_commit_signing_status(repo_root: String, sha: String) -> Result<SigningStatus, String> {
+    let output = Command::new("git")
+        .args(["verify-commit", "--raw", &sha])
+        .current_dir(&repo_root)
+        .output()
+        .map_err(|e| format!("Failed to verify commit: {}", e))?;
+
+    // With --raw, gpg's machine-readable status lines are written to stderr
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if stderr.contains("cannot run gpg") {
+        return Ok(SigningStatus {
+            is_signed: false,
+            signature_valid: None,
+            signer_key: None,
+            signer_identity: None,
+            message: Some("GPG is not installed; commit signatures cannot be verified".to_string()),
+        });
+    }
+
+    Ok(parse_gpg_status(&stderr))
+}
+
+/// Parse `[GNUPG:] GOODSIG|BADSIG|ERRSIG <key> [identity]` status lines.
+/// Expired signatures and expired or revoked keys count as invalid.
+fn parse_gpg_status(status: &str) -> SigningStatus {
+    for line in status.lines() {
+        let Some(rest) = line.strip_prefix("[GNUPG:] ") else {
+            continue;
+        };
+
+        let (keyword, args) = rest.split_once(' ').unwrap_or((rest, ""));
+        let (signature_valid, message) = match keyword {
+            "GOODSIG" => (Some(true), None),
+            "BADSIG" => (Some(false), None),
+            "EXPSIG" => (Some(false), Some("Signature has expired")),
+            "EXPKEYSIG" => (Some(false), Some("Signed with an expired key")),
+            "REVKEYSIG" => (Some(false), Some("Signed with a revoked key")),
+            "ERRSIG" => (None, None),
+            _ => continue,
+        };
+
+        let (key, identity) = match args.split_once(' ') {
+            Some((key, identity)) => (key, Some(identity)),
+            None => (args, None),
+        };
+
+        return SigningStatus {
+            is_signed: true,
+            signature_valid,
+            signer_key: Some(key.to_string()).filter(|k| !k.is_empty()),
+            // ERRSIG lines carry algorithm fields rather than a user id
+            signer_identity: if keyword == "ERRSIG" {
+                None
+            } else {
+                identity.map(|i| i.to_string())
+            },
+            message: message.map(|m| m.to_string()),
+        };
+    }
+
+    SigningStatus {
+        is_signed: false,
+        signature_valid: None,
+        signer_key: None,
+        signer_identity: None,
+        message: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn matches_sparse_patterns_cone_mode_top_level() {
+        let cone = patterns(&["/*", "!/*/", "/src/"]);
+        assert!(matches_sparse_patterns("README.md", &cone));
+        assert!(matches_sparse_patterns("src/main.rs", &cone));
+        assert!(matches_sparse_patterns("src/commands/git.rs", &cone));
+        assert!(!matches_sparse_patterns("docs/guide.md", &cone));
+        assert!(!matches_sparse_patterns("srcx/main.rs", &cone));
+    }
+
+    #[test]
+    fn matches_sparse_patterns_cone_mode_nested() {
+        let cone = patterns(&["/*", "!/*/", "/src/", "!/src/*/", "/src/app/"]);
+        assert!(matches_sparse_patterns("Cargo.toml", &cone));
+        assert!(matches_sparse_patterns("src/lib.rs", &cone));
+        assert!(matches_sparse_patterns("src/app/view.rs", &cone));
+        assert!(!matches_sparse_patterns("src/other/mod.rs", &cone));
+        assert!(!matches_sparse_patterns("tests/it.rs", &cone));
+    }
+
+    #[test]
+    fn matches_sparse_patterns_non_cone() {
+        let sparse = patterns(&["*.md", "!docs/", "lib/**/*.rs"]);
+        assert!(matches_sparse_patterns("README.md", &sparse));
+        assert!(matches_sparse_patterns("notes/todo.md", &sparse));
+        assert!(!matches_sparse_patterns("docs/guide.md", &sparse));
+        assert!(matches_sparse_patterns("lib/a/b.rs", &sparse));
+        assert!(!matches_sparse_patterns("src/main.rs", &sparse));
+    }
+
+    #[test]
+    fn matches_sparse_patterns_empty() {
+        assert!(!matches_sparse_patterns("README.md", &[]));
+    }
+
+    fn state_with_files(files: serde_json::Value) -> PersistedState {
+        serde_json::from_value(serde_json::json!({
+            "version": 1,
+            "sessionId": "abc",
+            "baseSha": "base",
+            "headSha": "head",
+            "files": files,
+            "ui": { "mode": "unified", "sidebarWidth": 280, "sidebarVisible": true },
+        }))
+        .unwrap()
+    }
+
+    fn file_state(viewed: bool, last_viewed_at: Option<&str>) -> serde_json::Value {
+        serde_json::json!({
+            "viewed": viewed,
+            "lastViewedSha": "",
+            "contentHash": "",
+            "diffStats": { "additions": 1, "deletions": 0 },
+            "collapseState": { "file": false, "hunks": [] },
+            "scrollPosition": 0,
+            "lastViewedAt": last_viewed_at,
+        })
+    }
+
+    #[test]
+    fn stamp_last_viewed_at_tracks_viewed_transitions() {
+        let previous = state_with_files(serde_json::json!({
+            "kept.rs": file_state(true, Some("2026-01-01T00:00:00+00:00")),
+            "legacy.rs": file_state(true, None),
+            "unviewed.rs": file_state(true, Some("2026-01-01T00:00:00+00:00")),
+            "new.rs": file_state(false, None),
+            "reviewed.rs": file_state(false, None),
+        }));
+        let mut state = state_with_files(serde_json::json!({
+            "kept.rs": file_state(true, None),
+            "legacy.rs": file_state(true, None),
+            "unviewed.rs": file_state(false, None),
+            "new.rs": file_state(true, None),
+            "reviewed.rs": file_state(true, Some("2025-12-01T00:00:00+00:00")),
+            "added.rs": file_state(true, None),
+        }));
+
+        let now = "2026-02-02T00:00:00+00:00";
+        stamp_last_viewed_at(&mut state, Some(&previous), now);
+
+        let at = |path: &str| state.files[path].last_viewed_at.clone();
+        assert_eq!(at("kept.rs").as_deref(), Some("2026-01-01T00:00:00+00:00"));
+        assert_eq!(at("legacy.rs"), None);
+        assert_eq!(at("unviewed.rs"), None);
+        assert_eq!(at("new.rs").as_deref(), Some(now));
+        assert_eq!(at("reviewed.rs").as_deref(), Some(now));
+        assert_eq!(at("added.rs").as_deref(), Some(now));
+    }
+
+    #[test]
+    fn session_lock_expires_after_ttl() {
+        let now = Utc::now();
+        let lock_at = |minutes_ago: i64| SessionLock {
+            window_label: "main".to_string(),
+            locked_at: (now - Duration::minutes(minutes_ago)).to_rfc3339(),
+        };
+
+        assert!(!is_session_lock_expired(&lock_at(1), now));
+        assert!(!is_session_lock_expired(
+            &lock_at(SESSION_LOCK_TTL_MINUTES),
+            now
+        ));
+        assert!(is_session_lock_expired(
+            &lock_at(SESSION_LOCK_TTL_MINUTES + 1),
+            now
+        ));
+
+        let unparseable = SessionLock {
+            window_label: "main".to_string(),
+            locked_at: "yesterday".to_string(),
+        };
+        assert!(is_session_lock_expired(&unparseable, now));
+    }
+
+    #[test]
+    fn parse_commit_body_splits_trailers_from_body() {
+        let message = concat!(
+            "Fix watcher reconnect\n",
+            "\n",
+            "The reconnect count never reset.\n",
+            "\n",
+            "Reset it after a healthy run.\n",
+            "\n",
+            "Co-authored-by: Jane Doe <jane@example.com>\n",
+            "Co-authored-by: Sam Roe <sam@example.com>\n",
+            "Signed-off-by: Alex Poe <alex@example.com>\n",
+        );
+
+        let parsed = parse_commit_body(message);
+        assert_eq!(parsed.subject, "Fix watcher reconnect");
+        assert_eq!(
+            parsed.body,
+            "The reconnect count never reset.\n\nReset it after a healthy run."
+        );
+        assert_eq!(
+            parsed.trailers["Co-authored-by"],
+            vec!["Jane Doe <jane@example.com>", "Sam Roe <sam@example.com>"]
+        );
+        assert_eq!(
+            parsed.trailers["Signed-off-by"],
+            vec!["Alex Poe <alex@example.com>"]
+        );
+    }
+
+    #[test]
+    fn parse_commit_body_keeps_mixed_final_paragraph() {
+        let message = "Update docs\n\nSee: the wiki for details\nand the changelog too\n";
+
+        let parsed = parse_commit_body(message);
+        assert_eq!(
+            parsed.body,
+            "See: the wiki for details\nand the changelog too"
+        );
+        assert!(parsed.trailers.is_empty());
+    }
+
+    #[test]
+    fn parse_commit_body_with_only_trailers_or_subject() {
+        let parsed =
+            parse_commit_body("Bump version\n\nSigned-off-by: Alex Poe <alex@example.com>\n");
+        assert_eq!(parsed.subject, "Bump version");
+        assert_eq!(parsed.body, "");
+        assert_eq!(parsed.trailers.len(), 1);
+
+        let parsed = parse_commit_body("Initial commit\n");
+        assert_eq!(parsed.subject, "Initial commit");
+        assert_eq!(parsed.body, "");
+        assert!(parsed.trailers.is_empty());
+    }
+
+    #[test]
+    fn normalize_remote_url_matches_ssh_and_https_forms() {
+        let expected = "github.com/owner/repo";
+        assert_eq!(
+            normalize_remote_url("git@github.com:owner/repo.git"),
+            expected
+        );
+        assert_eq!(
+            normalize_remote_url("https://github.com/owner/repo"),
+            expected
+        );
+        assert_eq!(
+            normalize_remote_url("https://token@github.com/Owner/Repo.git/"),
+            expected
+        );
+        assert_eq!(
+            normalize_remote_url("ssh://git@github.com/owner/repo.git"),
+            expected
+        );
+        assert_ne!(
+            normalize_remote_url("git@github.com:other/repo.git"),
+            expected
+        );
+    }
+
+    #[test]
+    fn parse_gpg_status_good_signature() {
+        let status = concat!(
+            "[GNUPG:] NEWSIG\n",
+            "[GNUPG:] KEY_CONSIDERED 5DE3E0509C47EA3CF04A42D34AEE18F83AFDEB23 0\n",
+            "[GNUPG:] SIG_ID 9Yk0bTUyAnLqGQfSzTr1Rw 2024-01-01 1704067200\n",
+            "[GNUPG:] GOODSIG 4AEE18F83AFDEB23 GitHub <noreply@github.com>\n",
+            "[GNUPG:] VALIDSIG 5DE3E0509C47EA3CF04A42D34AEE18F83AFDEB23 2024-01-01 1704067200 0 4 0 1 10 00 5DE3E0509C47EA3CF04A42D34AEE18F83AFDEB23\n",
+        );
+
+        let parsed = parse_gpg_status(status);
+        assert!(parsed.is_signed);
+        assert_eq!(parsed.signature_valid, Some(true));
+        assert_eq!(parsed.signer_key.as_deref(), Some("4AEE18F83AFDEB23"));
+        assert_eq!(
+            parsed.signer_identity.as_deref(),
+            Some("GitHub <noreply@github.com>")
+        );
+    }
+
+    #[test]
+    fn parse_gpg_status_bad_and_unverifiable_signatures() {
+        let parsed =
+            parse_gpg_status("[GNUPG:] BADSIG 4AEE18F83AFDEB23 Jane Doe <jane@example.com>\n");
+        assert!(parsed.is_signed);
+        assert_eq!(parsed.signature_valid, Some(false));
+        assert_eq!(
+            parsed.signer_identity.as_deref(),
+            Some("Jane Doe <jane@example.com>")
+        );
+
+        let parsed = parse_gpg_status(
+            "[GNUPG:] ERRSIG 4AEE18F83AFDEB23 1 8 00 1704067200 9 -\n[GNUPG:] NO_PUBKEY 4AEE18F83AFDEB23\n",
+        );
+        assert!(parsed.is_signed);
+        assert_eq!(parsed.signature_valid, None);
+        assert_eq!(parsed.signer_key.as_deref(), Some("4AEE18F83AFDEB23"));
+        assert_eq!(parsed.signer_identity, None);
+    }
+
+    #[test]
+    fn parse_gpg_status_expired_and_revoked_keys() {
+        for keyword in ["EXPSIG", "EXPKEYSIG", "REVKEYSIG"] {
+            let parsed = parse_gpg_status(&format!(
+                "[GNUPG:] {} 4AEE18F83AFDEB23 Jane Doe <jane@example.com>\n",
+                keyword
+            ));
+            assert!(parsed.is_signed, "{}", keyword);
+            assert_eq!(parsed.signature_valid, Some(false), "{}", keyword);
+            assert_eq!(parsed.signer_key.as_deref(), Some("4AEE18F83AFDEB23"));
+            assert_eq!(
+                parsed.signer_identity.as_deref(),
+                Some("Jane Doe <jane@example.com>")
+            );
+            assert!(parsed.message.is_some(), "{}", keyword);
+        }
+    }
+
+    #[test]
+    fn parse_gpg_status_unsigned() {
+        let parsed = parse_gpg_status("");
+        assert!(!parsed.is_signed);
+        assert_eq!(parsed.signature_valid, None);
+        assert_eq!(parsed.signer_key, None);
+    }
+
+    #[test]
+    fn parse_decorations_strips_head_and_tag_prefixes() {
+        assert_eq!(
+            parse_decorations("HEAD -> main, origin/main, origin/HEAD, tag: v1.0"),
+            vec!["main", "origin/main", "origin/HEAD", "v1.0"]
+        );
+        assert_eq!(
+            parse_decorations("HEAD, tag: v2.0.0-rc.1"),
+            vec!["v2.0.0-rc.1"]
+        );
+        assert!(parse_decorations("").is_empty());
+    }
+
+    #[test]
+    fn is_valid_config_key_accepts_section_keys() {
+        assert!(is_valid_config_key("user.name"));
+        assert!(is_valid_config_key("core.autocrlf"));
+        assert!(is_valid_config_key("branch.main.merge"));
+        assert!(is_valid_config_key("branch.feature/login-form.remote"));
+        assert!(is_valid_config_key("diff.rust.wordRegex"));
+    }
+
+    #[test]
+    fn is_valid_config_key_rejects_options_and_malformed_keys() {
+        assert!(!is_valid_config_key("--global"));
+        assert!(!is_valid_config_key("-c.user.name"));
+        assert!(!is_valid_config_key("user"));
+        assert!(!is_valid_config_key("user."));
+        assert!(!is_valid_config_key(".name"));
+        assert!(!is_valid_config_key("User.name"));
+        assert!(!is_valid_config_key("user.name --add"));
+        assert!(!is_valid_config_key("user.na\nme"));
+    }
+
+    #[test]
+    fn parse_check_ignore_handles_drive_letters_and_negation() {
+        let info = parse_check_ignore("C:\\Users\\me\\.gitignore\x003\x00*.log\x00a.log\x00");
+        assert!(info.is_ignored);
+        assert_eq!(info.matching_pattern.as_deref(), Some("*.log"));
+        assert_eq!(
+            info.source_file.as_deref(),
+            Some("C:\\Users\\me\\.gitignore:3")
+        );
+
+        let info = parse_check_ignore(".gitignore\x007\x00!keep.log\x00keep.log\x00");
+        assert!(!info.is_ignored);
+
+        let info = parse_check_ignore("");
+        assert!(!info.is_ignored);
+        assert_eq!(info.source_file, None);
+    }
+}
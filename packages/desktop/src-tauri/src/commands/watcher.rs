@@ -1,12 +1,19 @@
+use chrono::{DateTime, Utc};
 use notify::event::{CreateKind, ModifyKind, RemoveKind};
-use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use notify::{
+    Config, Event, EventHandler, EventKind, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher,
+};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter, Manager};
 
+use super::git::{set_diff_cache_capacity, DEFAULT_DIFF_CACHE_CAPACITY};
+use super::session::glob_match;
+
 /// Change event emitted to the frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChangeEvent {
@@ -17,22 +24,124 @@ pub struct ChangeEvent {
     pub paths: Option<Vec<String>>,
     #[serde(rename = "newHeadSha")]
     pub new_head_sha: Option<String>,
+    /// Too many paths changed to list; `paths` is None and the frontend should
+    /// do a full refresh
+    #[serde(default)]
+    pub truncated: bool,
+    #[serde(rename = "totalPathCount", default)]
+    pub total_path_count: u32,
+}
+
+/// Emitted as "watcher-error" when the OS watcher fails and a reconnect is
+/// scheduled, again if recreating the watcher fails, and when config hot-reload
+/// can't be set up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatcherErrorEvent {
+    #[serde(rename = "repoRoot")]
+    pub repo_root: String,
+    #[serde(rename = "errorMessage")]
+    pub error_message: String,
+    #[serde(rename = "reconnectAttempts")]
+    pub reconnect_attempts: u32,
+}
+
+/// User-configured glob filters, matched against repo-relative paths
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatcherPatterns {
+    /// When non-empty, a path must match at least one of these
+    #[serde(rename = "includePatterns")]
+    pub include_patterns: Vec<String>,
+    #[serde(rename = "excludePatterns")]
+    pub exclude_patterns: Vec<String>,
+}
+
+impl WatcherPatterns {
+    fn allows(&self, relative_path: &str) -> bool {
+        if !self.include_patterns.is_empty()
+            && !self
+                .include_patterns
+                .iter()
+                .any(|p| glob_match(p, relative_path))
+        {
+            return false;
+        }
+
+        !self
+            .exclude_patterns
+            .iter()
+            .any(|p| glob_match(p, relative_path))
+    }
+}
+
+/// Result of `ping_watcher`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatcherHealth {
+    #[serde(rename = "isActive")]
+    pub is_active: bool,
+    #[serde(rename = "uptimeSecs")]
+    pub uptime_secs: u64,
+    #[serde(rename = "eventsReceived")]
+    pub events_received: u64,
+    #[serde(rename = "lastEventAt")]
+    pub last_event_at: Option<String>,
+}
+
+/// One entry in `get_watcher_status`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatcherInfo {
+    #[serde(rename = "repoRoot")]
+    pub repo_root: String,
+    #[serde(rename = "isPaused")]
+    pub is_paused: bool,
+    #[serde(rename = "uptimeSecs")]
+    pub uptime_secs: u64,
+    #[serde(rename = "eventCount")]
+    pub event_count: u64,
+    /// "native" (inotify/FSEvents/...) or "polling"
+    pub backend: String,
+}
+
+/// The built-in ignore rules applied before user patterns, from `get_watcher_default_ignores`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatcherIgnoreDefaults {
+    /// Directory names ignored along with everything under them
+    pub dirs: Vec<String>,
+    /// Exact file names
+    pub files: Vec<String>,
+    pub suffixes: Vec<String>,
+    pub prefixes: Vec<String>,
 }
 
 /// Manages file watchers for repositories
 pub struct WatcherManager {
     /// Map of repo_root -> watcher instance
     watchers: Mutex<HashMap<String, WatcherState>>,
+    /// Repos waiting out `RECONNECT_DELAY` after an error; `stop_watching`
+    /// removes the entry to cancel the reconnect
+    reconnecting: Mutex<HashSet<String>>,
 }
 
 struct WatcherState {
-    _watcher: RecommendedWatcher,
+    _watcher: Box<dyn Watcher + Send>,
+    /// Watches `.revi/` for config edits, which the repo watcher ignores
+    _config_watcher: Option<Box<dyn Watcher + Send>>,
+    /// Set when using the polling backend
+    poll_interval: Option<Duration>,
+    /// Times this repo's watcher has been recreated after an error; a watcher
+    /// that stayed up for `RECONNECT_RESET_AFTER` before failing starts the count over
+    reconnect_attempts: u32,
+    patterns: WatcherPatterns,
+    start_time: Instant,
+    /// Raw OS events received, before any filtering
+    events_received: Arc<AtomicU64>,
+    last_event_at: Arc<Mutex<Option<DateTime<Utc>>>>,
 }
 
 impl WatcherManager {
     pub fn new() -> Self {
         Self {
             watchers: Mutex::new(HashMap::new()),
+            reconnecting: Mutex::new(HashSet::new()),
         }
     }
 }
@@ -81,6 +190,25 @@ const IGNORED_PREFIXES: &[&str] = &[
     "#", // Emacs auto-save
 ];
 
+/// Poll interval when `watcher.polling` is enabled without `watcher.pollIntervalMs`
+const DEFAULT_POLL_INTERVAL_MS: u64 = 2000;
+
+/// Debounce window when `watcher.debounceMs` is not set
+const DEFAULT_DEBOUNCE_MS: u64 = 500;
+
+/// Maximum number of paths listed in a single file_changed event
+const MAX_EVENT_PATHS: usize = 100;
+
+/// Delay before recreating a watcher that reported an error
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+/// Give up reconnecting after this many attempts
+const MAX_RECONNECT_ATTEMPTS: u32 = 5;
+
+/// A watcher that ran this long before failing was healthy, so its failure
+/// isn't counted with the ones before it
+const RECONNECT_RESET_AFTER: Duration = Duration::from_secs(300);
+
 /// Git ref paths we selectively allow through the .git/ ignore rule.
 /// Changes to these indicate branch switches, commits, rebases, etc.
 fn is_git_ref_path(relative_path: &str) -> bool {
@@ -110,7 +238,7 @@ fn read_head_sha(repo_root: &Path) -> Option<String> {
 }
 
 /// Check if a path should be ignored
-fn should_ignore(path: &Path, repo_root: &Path) -> bool {
+fn should_ignore(path: &Path, repo_root: &Path, patterns: &WatcherPatterns) -> bool {
     let relative = match path.strip_prefix(repo_root) {
         Ok(p) => p,
         Err(_) => return true, // Outside repo = ignore
@@ -159,7 +287,8 @@ fn should_ignore(path: &Path, repo_root: &Path) -> bool {
         }
     }
 
-    false
+    // User-configured include/exclude patterns
+    !patterns.allows(&path_str)
 }
 
 /// Check if an event kind represents an actual content change
@@ -192,9 +321,161 @@ fn is_content_change(kind: &EventKind) -> bool {
     }
 }
 
+fn repo_config_path(repo_root: &Path) -> PathBuf {
+    repo_root.join(".revi").join("config.json")
+}
+
+/// Load `.revi/config.json`; None if it's missing or not valid JSON
+fn load_repo_config(repo_root: &Path) -> Option<serde_json::Value> {
+    std::fs::read_to_string(repo_config_path(repo_root))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+/// Read `watcher.polling` / `watcher.pollIntervalMs` from `.revi/config.json`
+fn read_polling_config(repo_root: &Path) -> (bool, Option<u64>) {
+    let config = match load_repo_config(repo_root) {
+        Some(config) => config,
+        None => return (false, None),
+    };
+
+    let watcher = &config["watcher"];
+    (
+        watcher["polling"].as_bool().unwrap_or(false),
+        watcher["pollIntervalMs"].as_u64(),
+    )
+}
+
+/// Read `watcher.debounceMs` from a loaded config
+fn debounce_ms_from_config(config: Option<&serde_json::Value>) -> u64 {
+    config
+        .and_then(|config| config["watcher"]["debounceMs"].as_u64())
+        .unwrap_or(DEFAULT_DEBOUNCE_MS)
+}
+
+/// Read `cache.diffCacheSize` from a loaded config (the diff cache is app-wide)
+fn diff_cache_capacity_from_config(config: Option<&serde_json::Value>) -> usize {
+    config
+        .and_then(|config| config["cache"]["diffCacheSize"].as_u64())
+        .filter(|&size| size > 0)
+        .map_or(DEFAULT_DIFF_CACHE_CAPACITY, |size| size as usize)
+}
+
+/// Create a native or polling watcher depending on `poll_interval`
+fn create_watcher<F: EventHandler>(
+    event_handler: F,
+    poll_interval: Option<Duration>,
+) -> Result<Box<dyn Watcher + Send>, String> {
+    let watcher: Box<dyn Watcher + Send> = match poll_interval {
+        Some(interval) => Box::new(
+            PollWatcher::new(
+                event_handler,
+                Config::default().with_poll_interval(interval),
+            )
+            .map_err(|e| format!("Failed to create watcher: {}", e))?,
+        ),
+        None => Box::new(
+            RecommendedWatcher::new(
+                event_handler,
+                Config::default().with_poll_interval(Duration::from_secs(2)), // Poll less frequently
+            )
+            .map_err(|e| format!("Failed to create watcher: {}", e))?,
+        ),
+    };
+    Ok(watcher)
+}
+
+/// Watch `.revi/` for edits to `config.json`. On change, re-read the config, apply the
+/// live settings (`watcher.debounceMs`, `cache.diffCacheSize`) and emit "config-reloaded"
+/// with the new config as payload for the frontend's own settings.
+/// `watcher.polling` and `watcher.pollIntervalMs` need a new watcher, so they apply the
+/// next time the repo is watched.
+/// Watches the directory rather than the file because editors often save by rename;
+/// `.revi/` is created if missing so a config added later is still picked up.
+fn watch_config_file(
+    app_handle: &AppHandle,
+    repo_path: &Path,
+    debounce_ms: Arc<AtomicU64>,
+    poll_interval: Option<Duration>,
+) -> Result<Box<dyn Watcher + Send>, String> {
+    let revi_dir = repo_path.join(".revi");
+    std::fs::create_dir_all(&revi_dir)
+        .map_err(|e| format!("Failed to create .revi directory: {}", e))?;
+
+    let repo_path = repo_path.to_path_buf();
+    let app_handle = app_handle.clone();
+    let event_handler = move |res: Result<Event, notify::Error>| {
+        let Ok(event) = res else {
+            return;
+        };
+        if !is_content_change(&event.kind)
+            || !event
+                .paths
+                .iter()
+                .any(|p| p.file_name().is_some_and(|name| name == "config.json"))
+        {
+            return;
+        }
+
+        // A half-written file fails to parse; the editor's final write triggers another event
+        let Some(config) = load_repo_config(&repo_path) else {
+            return;
+        };
+        debounce_ms.store(debounce_ms_from_config(Some(&config)), Ordering::Relaxed);
+        set_diff_cache_capacity(diff_cache_capacity_from_config(Some(&config)));
+        let _ = app_handle.emit("config-reloaded", config);
+    };
+
+    let mut watcher = create_watcher(event_handler, poll_interval)?;
+    watcher
+        .watch(&revi_dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch config: {}", e))?;
+
+    Ok(watcher)
+}
+
+/// List the built-in ignore rules, e.g. to show them in a settings UI
+#[tauri::command]
+pub fn get_watcher_default_ignores() -> WatcherIgnoreDefaults {
+    let to_strings =
+        |items: &[&str]| -> Vec<String> { items.iter().map(|s| s.to_string()).collect() };
+    WatcherIgnoreDefaults {
+        dirs: to_strings(IGNORED_DIRS),
+        files: to_strings(IGNORED_FILES),
+        suffixes: to_strings(IGNORED_SUFFIXES),
+        prefixes: to_strings(IGNORED_PREFIXES),
+    }
+}
+
 /// Start watching a repository for changes
+/// `include_patterns` / `exclude_patterns` are globs applied after the built-in ignore rules.
+/// `use_polling` (or `watcher.polling` in `.revi/config.json`) switches to a polling
+/// backend for filesystems where native events are unreliable (NFS, CIFS, Docker mounts).
 #[tauri::command]
-pub fn start_watching(app_handle: AppHandle, repo_root: String) -> Result<(), String> {
+pub fn start_watching(
+    app_handle: AppHandle,
+    repo_root: String,
+    include_patterns: Vec<String>,
+    exclude_patterns: Vec<String>,
+    use_polling: bool,
+) -> Result<(), String> {
+    let patterns = WatcherPatterns {
+        include_patterns,
+        exclude_patterns,
+    };
+    let (config_polling, config_interval_ms) = read_polling_config(Path::new(&repo_root));
+    let poll_interval = (use_polling || config_polling)
+        .then(|| Duration::from_millis(config_interval_ms.unwrap_or(DEFAULT_POLL_INTERVAL_MS)));
+    start_watching_with_attempts(app_handle, repo_root, patterns, poll_interval, 0)
+}
+
+fn start_watching_with_attempts(
+    app_handle: AppHandle,
+    repo_root: String,
+    patterns: WatcherPatterns,
+    poll_interval: Option<Duration>,
+    reconnect_attempts: u32,
+) -> Result<(), String> {
     let manager = app_handle.state::<WatcherManager>();
     let mut watchers = manager.watchers.lock().map_err(|e| e.to_string())?;
 
@@ -207,9 +488,15 @@ pub fn start_watching(app_handle: AppHandle, repo_root: String) -> Result<(), St
     let repo_root_clone = repo_root.clone();
     let app_handle_clone = app_handle.clone();
 
-    // Debounce: collect events over this window before emitting
-    let debounce_duration = Duration::from_millis(500);
-    let last_emit = std::sync::Arc::new(Mutex::new(Instant::now() - debounce_duration));
+    // Debounce: collect events over this window before emitting.
+    // Shared with the config watcher so `watcher.debounceMs` edits apply live.
+    let config = load_repo_config(&repo_path);
+    let debounce_ms = Arc::new(AtomicU64::new(debounce_ms_from_config(config.as_ref())));
+    set_diff_cache_capacity(diff_cache_capacity_from_config(config.as_ref()));
+    let debounce_ms_clone = debounce_ms.clone();
+    let last_emit = std::sync::Arc::new(Mutex::new(
+        Instant::now() - Duration::from_millis(debounce_ms.load(Ordering::Relaxed)),
+    ));
     let last_head_sha = std::sync::Arc::new(Mutex::new(read_head_sha(&repo_path)));
 
     // Track if we have pending changes (for coalescing rapid events)
@@ -219,35 +506,139 @@ pub fn start_watching(app_handle: AppHandle, repo_root: String) -> Result<(), St
     let last_emit_clone = last_emit.clone();
     let last_head_sha_clone = last_head_sha.clone();
     let repo_path_clone = repo_path.clone();
+    let patterns_clone = patterns.clone();
+
+    // Health stats for ping_watcher
+    let events_received = Arc::new(AtomicU64::new(0));
+    let last_event_at = Arc::new(Mutex::new(None));
+    let events_received_clone = events_received.clone();
+    let last_event_at_clone = last_event_at.clone();
+
+    let event_handler = move |res: Result<Event, notify::Error>| match res {
+        Ok(event) => {
+            events_received_clone.fetch_add(1, Ordering::Relaxed);
+            *last_event_at_clone
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()) = Some(Utc::now());
+            handle_event(
+                event,
+                &repo_path_clone,
+                &app_handle_clone,
+                &last_emit_clone,
+                &last_head_sha_clone,
+                &pending_clone,
+                &patterns_clone,
+                Duration::from_millis(debounce_ms_clone.load(Ordering::Relaxed)),
+            )
+        }
+        Err(error) => handle_error(error, &repo_path_clone, &app_handle_clone),
+    };
 
-    let watcher = RecommendedWatcher::new(
-        move |res: Result<Event, notify::Error>| {
-            if let Ok(event) = res {
-                handle_event(
-                    event,
-                    &repo_path_clone,
-                    &app_handle_clone,
-                    &last_emit_clone,
-                    &last_head_sha_clone,
-                    &pending_clone,
-                    debounce_duration,
-                );
-            }
-        },
-        Config::default().with_poll_interval(Duration::from_secs(2)), // Poll less frequently
-    )
-    .map_err(|e| format!("Failed to create watcher: {}", e))?;
-
-    let mut watcher = watcher;
+    let mut watcher = create_watcher(event_handler, poll_interval)?;
     watcher
         .watch(Path::new(&repo_root), RecursiveMode::Recursive)
         .map_err(|e| format!("Failed to watch path: {}", e))?;
 
-    watchers.insert(repo_root_clone, WatcherState { _watcher: watcher });
+    // Config hot-reload is best-effort; the repo watcher works without it
+    let config_watcher = watch_config_file(&app_handle, &repo_path, debounce_ms, poll_interval)
+        .map_err(|error_message| {
+            let _ = app_handle.emit(
+                "watcher-error",
+                WatcherErrorEvent {
+                    repo_root: repo_root.clone(),
+                    error_message,
+                    reconnect_attempts,
+                },
+            );
+        })
+        .ok();
+
+    watchers.insert(
+        repo_root_clone,
+        WatcherState {
+            _watcher: watcher,
+            _config_watcher: config_watcher,
+            poll_interval,
+            reconnect_attempts,
+            patterns,
+            start_time: Instant::now(),
+            events_received,
+            last_event_at,
+        },
+    );
 
     Ok(())
 }
 
+/// Health check: is the repo still being watched, and is it seeing events?
+#[tauri::command]
+pub fn ping_watcher(app_handle: AppHandle, repo_root: String) -> Result<WatcherHealth, String> {
+    let manager = app_handle.state::<WatcherManager>();
+    let watchers = manager.watchers.lock().map_err(|e| e.to_string())?;
+
+    let health = match watchers.get(&repo_root) {
+        Some(state) => WatcherHealth {
+            is_active: true,
+            uptime_secs: state.start_time.elapsed().as_secs(),
+            events_received: state.events_received.load(Ordering::Relaxed),
+            last_event_at: state
+                .last_event_at
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .map(|at| at.to_rfc3339()),
+        },
+        None => WatcherHealth {
+            is_active: false,
+            uptime_secs: 0,
+            events_received: 0,
+            last_event_at: None,
+        },
+    };
+
+    Ok(health)
+}
+
+/// List every repository currently being watched, sorted by path
+#[tauri::command]
+pub fn get_watcher_status(app_handle: AppHandle) -> Result<Vec<WatcherInfo>, String> {
+    let manager = app_handle.state::<WatcherManager>();
+    let watchers = manager.watchers.lock().map_err(|e| e.to_string())?;
+
+    let mut infos: Vec<WatcherInfo> = watchers
+        .iter()
+        .map(|(repo_root, state)| WatcherInfo {
+            repo_root: repo_root.clone(),
+            // Watchers run until stopped; there is no paused state yet
+            is_paused: false,
+            uptime_secs: state.start_time.elapsed().as_secs(),
+            event_count: state.events_received.load(Ordering::Relaxed),
+            backend: if state.poll_interval.is_some() {
+                "polling".to_string()
+            } else {
+                "native".to_string()
+            },
+        })
+        .collect();
+    infos.sort_by(|a, b| a.repo_root.cmp(&b.repo_root));
+
+    Ok(infos)
+}
+
+/// Get the include/exclude patterns a repository is being watched with
+#[tauri::command]
+pub fn get_watcher_patterns(
+    app_handle: AppHandle,
+    repo_root: String,
+) -> Result<WatcherPatterns, String> {
+    let manager = app_handle.state::<WatcherManager>();
+    let watchers = manager.watchers.lock().map_err(|e| e.to_string())?;
+
+    watchers
+        .get(&repo_root)
+        .map(|state| state.patterns.clone())
+        .ok_or_else(|| format!("Not watching {}", repo_root))
+}
+
 /// Stop watching a repository
 #[tauri::command]
 pub fn stop_watching(app_handle: AppHandle, repo_root: String) -> Result<(), String> {
@@ -256,11 +647,88 @@ pub fn stop_watching(app_handle: AppHandle, repo_root: String) -> Result<(), Str
 
     // Remove watcher - it will be dropped and stop watching
     watchers.remove(&repo_root);
+    // Cancel a reconnect still waiting out its delay
+    manager
+        .reconnecting
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&repo_root);
 
     Ok(())
 }
 
+/// Handle a watcher error (e.g. inotify watch limit exceeded) by dropping the
+/// watcher and recreating it after `RECONNECT_DELAY`.
+/// Runs on a separate thread: the watcher can't be dropped from its own callback.
+fn handle_error(error: notify::Error, repo_root: &Path, app_handle: &AppHandle) {
+    let repo_root = repo_root.to_string_lossy().to_string();
+    let app_handle = app_handle.clone();
+
+    std::thread::spawn(move || {
+        let manager = app_handle.state::<WatcherManager>();
+        let (previous_attempts, patterns, poll_interval) = {
+            let mut watchers = manager.watchers.lock().unwrap_or_else(|e| e.into_inner());
+            match watchers.remove(&repo_root) {
+                Some(state) => {
+                    let previous_attempts = if state.start_time.elapsed() >= RECONNECT_RESET_AFTER {
+                        0
+                    } else {
+                        state.reconnect_attempts
+                    };
+                    (previous_attempts, state.patterns, state.poll_interval)
+                }
+                // Already stopped, or another error is already reconnecting
+                None => return,
+            }
+        };
+
+        let reconnect_attempts = previous_attempts + 1;
+        let emit_error = |error_message: String| {
+            let _ = app_handle.emit(
+                "watcher-error",
+                WatcherErrorEvent {
+                    repo_root: repo_root.clone(),
+                    error_message,
+                    reconnect_attempts,
+                },
+            );
+        };
+        emit_error(error.to_string());
+
+        if reconnect_attempts > MAX_RECONNECT_ATTEMPTS {
+            return;
+        }
+
+        manager
+            .reconnecting
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(repo_root.clone());
+        std::thread::sleep(RECONNECT_DELAY);
+        // stop_watching ran while we waited
+        if !manager
+            .reconnecting
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&repo_root)
+        {
+            return;
+        }
+
+        if let Err(e) = start_watching_with_attempts(
+            app_handle.clone(),
+            repo_root.clone(),
+            patterns,
+            poll_interval,
+            reconnect_attempts,
+        ) {
+            emit_error(format!("Failed to restart file watcher: {}", e));
+        }
+    });
+}
+
 /// Handle a file system event
+#[allow(clippy::too_many_arguments)]
 fn handle_event(
     event: Event,
     repo_root: &Path,
@@ -268,6 +736,7 @@ fn handle_event(
     last_emit: &std::sync::Arc<Mutex<Instant>>,
     last_head_sha: &std::sync::Arc<Mutex<Option<String>>>,
     pending_change: &std::sync::Arc<Mutex<bool>>,
+    patterns: &WatcherPatterns,
     debounce_duration: Duration,
 ) {
     // Only process actual content changes
@@ -279,7 +748,7 @@ fn handle_event(
     let relevant_paths: Vec<PathBuf> = event
         .paths
         .iter()
-        .filter(|p| !should_ignore(p, repo_root))
+        .filter(|p| !should_ignore(p, repo_root, patterns))
         .cloned()
         .collect();
 
@@ -359,6 +828,8 @@ fn handle_event(
                 repo_root: repo_root.to_string_lossy().to_string(),
                 paths: None,
                 new_head_sha,
+                truncated: false,
+                total_path_count: 0,
             };
             let _ = app_handle.emit("repo-changed", ref_event);
         }
@@ -372,11 +843,17 @@ fn handle_event(
             .map(|p| p.to_string_lossy().to_string())
             .collect();
 
+        // Thousands of paths (e.g. a build) would bog down the frontend's deserializer
+        let total_path_count = paths.len() as u32;
+        let truncated = paths.len() > MAX_EVENT_PATHS;
+
         let change_event = ChangeEvent {
             event_type: "file_changed".to_string(),
             repo_root: repo_root.to_string_lossy().to_string(),
-            paths: Some(paths),
+            paths: if truncated { None } else { Some(paths) },
             new_head_sha: None,
+            truncated,
+            total_path_count,
         };
         let _ = app_handle.emit("repo-changed", change_event);
     }
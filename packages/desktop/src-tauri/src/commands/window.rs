@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use std::fs;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Mutex;
-use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+use tauri::{AppHandle, Emitter, Manager, Monitor, WebviewUrl, WebviewWindowBuilder};
 
 // Reasonable bounds for window dimensions to prevent corrupted state
 const MIN_WIDTH: f64 = 800.0;
@@ -12,6 +12,13 @@ const MIN_HEIGHT: f64 = 600.0;
 const MAX_HEIGHT: f64 = 5000.0;
 const DEFAULT_WIDTH: f64 = 1400.0;
 const DEFAULT_HEIGHT: f64 = 900.0;
+const PICKER_WIDTH: f64 = 600.0;
+const PICKER_HEIGHT: f64 = 400.0;
+const MIN_FONT_SIZE: f64 = 8.0;
+const MAX_FONT_SIZE: f64 = 32.0;
+const MIN_ZOOM: f64 = 0.5;
+const MAX_ZOOM: f64 = 3.0;
+const DEFAULT_ZOOM: f64 = 1.0;
 
 /// Screen bounds for clamping window dimensions and position
 #[derive(Debug, Clone, Copy)]
@@ -29,16 +36,20 @@ impl ScreenBounds {
         // (monitors are queried via windows in Tauri)
         let window = app.webview_windows().into_values().next()?;
         let monitor = window.primary_monitor().ok()??;
+        Some(Self::from_monitor(&monitor))
+    }
+
+    fn from_monitor(monitor: &Monitor) -> Self {
         let size = monitor.size();
         let position = monitor.position();
         let scale = monitor.scale_factor();
 
-        Some(ScreenBounds {
+        ScreenBounds {
             x: position.x as f64,
             y: position.y as f64,
             width: size.width as f64 / scale,
             height: size.height as f64 / scale,
-        })
+        }
     }
 
     /// Clamp dimensions to fit within screen bounds (with some margin for window chrome)
@@ -99,7 +110,19 @@ fn sanitize_dimensions_for_screen(
     }
 }
 
+/// Get bounds for every connected monitor, or an empty list if unavailable
+fn get_all_monitor_bounds(app: &AppHandle) -> Vec<ScreenBounds> {
+    app.webview_windows()
+        .into_values()
+        .next()
+        .and_then(|window| window.available_monitors().ok())
+        .map(|monitors| monitors.iter().map(ScreenBounds::from_monitor).collect())
+        .unwrap_or_default()
+}
+
 /// Sanitize position for screen bounds
+/// The position is kept if it is visible on any connected monitor (falling back to the
+/// primary screen when the monitor list is unavailable).
 /// Returns Some((x, y)) if position is valid, None if window should use default positioning
 fn sanitize_position_for_screen(
     x: Option<f64>,
@@ -107,9 +130,17 @@ fn sanitize_position_for_screen(
     width: f64,
     height: f64,
     screen: Option<ScreenBounds>,
+    monitors: &[ScreenBounds],
 ) -> Option<(f64, f64)> {
     let (px, py) = (x?, y?);
 
+    if !monitors.is_empty() {
+        // None here means the saved monitor is gone; center on the primary instead
+        return monitors
+            .iter()
+            .find_map(|bounds| bounds.clamp_position(px, py, width, height));
+    }
+
     match screen {
         Some(bounds) => bounds.clamp_position(px, py, width, height),
         None => None, // No screen info, let system position the window
@@ -121,6 +152,12 @@ pub fn is_valid_dimension(value: f64, min: f64, max: f64) -> bool {
     value >= min && value <= max && value.is_finite()
 }
 
+/// Check whether a window should be restored maximized.
+/// Saved size/position are the pre-maximize values, so they are skipped in that case.
+pub fn is_valid_maximized(info: &WindowInfo) -> bool {
+    info.maximized
+}
+
 /// Check if width is within valid bounds
 pub fn is_valid_width(width: f64) -> bool {
     is_valid_dimension(width, MIN_WIDTH, MAX_WIDTH)
@@ -142,6 +179,23 @@ pub struct WindowInfo {
     pub y: Option<f64>,
     pub width: Option<f64>,
     pub height: Option<f64>,
+    #[serde(default)]
+    pub maximized: bool,
+    #[serde(rename = "fontSize")]
+    pub font_size: Option<f64>,
+    pub theme: Option<String>,
+    #[serde(rename = "zoomLevel", default)]
+    pub zoom_level: Option<f64>,
+}
+
+/// Per-window display settings sent with the "window-settings-changed" event
+#[derive(Debug, Clone, Serialize)]
+pub struct WindowSettings {
+    #[serde(rename = "fontSize")]
+    pub font_size: Option<f64>,
+    pub theme: Option<String>,
+    #[serde(rename = "zoomLevel")]
+    pub zoom_level: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -193,6 +247,10 @@ pub fn create_window(app: AppHandle) -> Result<String, String> {
             y: None,
             width: Some(DEFAULT_WIDTH),
             height: Some(DEFAULT_HEIGHT),
+            maximized: false,
+            font_size: None,
+            theme: None,
+            zoom_level: None,
         },
     );
 
@@ -219,6 +277,10 @@ pub fn register_window_session(
             y: None,
             width: None,
             height: None,
+            maximized: false,
+            font_size: None,
+            theme: None,
+            zoom_level: None,
         });
 
     entry.repo_path = repo_path;
@@ -227,6 +289,143 @@ pub fn register_window_session(
     Ok(())
 }
 
+/// Percent-encode a value for use in a URL query string
+fn encode_query_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Open a small modal-like window for choosing between sessions of a repo.
+/// The picker emits "session-selected" to `parent_label` and then closes itself.
+/// It is not tracked in WindowManager, so it is never persisted or restored.
+#[tauri::command]
+pub fn open_session_picker(
+    app: AppHandle,
+    repo_root: String,
+    parent_label: String,
+) -> Result<String, String> {
+    let parent = app
+        .get_webview_window(&parent_label)
+        .ok_or_else(|| format!("Window '{}' not found", parent_label))?;
+
+    let manager = app.state::<WindowManager>();
+    let n = manager.counter.fetch_add(1, Ordering::SeqCst);
+    let label = format!("session-picker-{}", n);
+
+    let url = format!(
+        "index.html?view=session-picker&repoRoot={}&parent={}",
+        encode_query_value(&repo_root),
+        encode_query_value(&parent_label)
+    );
+
+    WebviewWindowBuilder::new(&app, &label, WebviewUrl::App(url.into()))
+        .title("Select Session")
+        .inner_size(PICKER_WIDTH, PICKER_HEIGHT)
+        .resizable(false)
+        .center()
+        .parent(&parent)
+        .map_err(|e| format!("Failed to attach picker to parent window: {}", e))?
+        .build()
+        .map_err(|e| format!("Failed to create session picker: {}", e))?;
+
+    Ok(label)
+}
+
+/// Update a window's settings, persist them, and notify that window
+fn update_window_settings(
+    app: &AppHandle,
+    window_label: &str,
+    update: impl FnOnce(&mut WindowInfo),
+) -> Result<(), String> {
+    let settings = {
+        let manager = app.state::<WindowManager>();
+        let mut windows = manager.windows.lock().unwrap_or_else(|e| e.into_inner());
+        let info = windows
+            .get_mut(window_label)
+            .ok_or_else(|| format!("Window '{}' not found", window_label))?;
+        update(info);
+        WindowSettings {
+            font_size: info.font_size,
+            theme: info.theme.clone(),
+            zoom_level: info.zoom_level,
+        }
+    };
+
+    persist_states_sync(app)?;
+
+    app.emit_to(window_label, "window-settings-changed", settings)
+        .map_err(|e| format!("Failed to emit window settings: {}", e))
+}
+
+/// Override the font size for a single window
+#[tauri::command]
+pub fn set_window_font_size(
+    app: AppHandle,
+    window_label: String,
+    font_size: f64,
+) -> Result<(), String> {
+    if !is_valid_dimension(font_size, MIN_FONT_SIZE, MAX_FONT_SIZE) {
+        return Err(format!(
+            "Font size must be between {} and {}",
+            MIN_FONT_SIZE, MAX_FONT_SIZE
+        ));
+    }
+
+    update_window_settings(&app, &window_label, |info| info.font_size = Some(font_size))
+}
+
+/// Clamp a zoom factor to the supported range; non-finite values reset to 100%
+fn clamp_zoom(zoom: f64) -> f64 {
+    if zoom.is_finite() {
+        zoom.clamp(MIN_ZOOM, MAX_ZOOM)
+    } else {
+        DEFAULT_ZOOM
+    }
+}
+
+/// Set a window's webview zoom (clamped to 0.5-3.0) and persist it
+#[tauri::command]
+pub fn set_window_zoom_level(
+    app: AppHandle,
+    window_label: String,
+    zoom: f64,
+) -> Result<(), String> {
+    let zoom = clamp_zoom(zoom);
+    let window = app
+        .get_webview_window(&window_label)
+        .ok_or_else(|| format!("Window '{}' not found", window_label))?;
+    window
+        .set_zoom(zoom)
+        .map_err(|e| format!("Failed to set zoom: {}", e))?;
+
+    update_window_settings(&app, &window_label, |info| info.zoom_level = Some(zoom))
+}
+
+/// Get a window's zoom level, 1.0 if it was never changed
+#[tauri::command]
+pub fn get_window_zoom_level(app: AppHandle, window_label: String) -> Result<f64, String> {
+    let manager = app.state::<WindowManager>();
+    let windows = manager.windows.lock().unwrap_or_else(|e| e.into_inner());
+    let info = windows
+        .get(&window_label)
+        .ok_or_else(|| format!("Window '{}' not found", window_label))?;
+    Ok(info.zoom_level.unwrap_or(DEFAULT_ZOOM))
+}
+
+/// Override the theme for a single window
+#[tauri::command]
+pub fn set_window_theme(app: AppHandle, window_label: String, theme: String) -> Result<(), String> {
+    update_window_settings(&app, &window_label, |info| info.theme = Some(theme))
+}
+
 #[tauri::command]
 pub fn save_window_states(app: AppHandle) -> Result<(), String> {
     persist_states_sync(&app)
@@ -330,6 +529,53 @@ pub fn focus_window_and_close(
     Ok(())
 }
 
+/// Close every open window, optionally persisting window states first.
+/// On macOS the app keeps running (see `RunEvent::ExitRequested` in main.rs).
+/// Returns the number of windows closed.
+#[tauri::command]
+pub fn close_all_windows(app: AppHandle, save_state: bool) -> Result<u32, String> {
+    if save_state {
+        persist_states_sync(&app)?;
+    }
+
+    let mut count = 0;
+    for window in app.webview_windows().values() {
+        // `close` fires CloseRequested, which would persist again after each
+        // window is unregistered and leave a near-empty saved set; `destroy`
+        // skips it so the states saved above are kept
+        window
+            .destroy()
+            .map_err(|e| format!("Failed to close window '{}': {}", window.label(), e))?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Emit an event to every open window except `exclude_label`.
+/// Returns the number of windows the event was delivered to.
+#[tauri::command]
+pub fn broadcast_to_windows(
+    app: AppHandle,
+    event_name: String,
+    payload: serde_json::Value,
+    exclude_label: Option<String>,
+) -> Result<u32, String> {
+    let mut count = 0;
+
+    for label in app.webview_windows().keys() {
+        if exclude_label.as_deref() == Some(label.as_str()) {
+            continue;
+        }
+
+        app.emit_to(label.as_str(), &event_name, payload.clone())
+            .map_err(|e| format!("Failed to emit to window '{}': {}", label, e))?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
 /// Persist current window states to disk. Called from event handlers.
 pub fn persist_states_sync(app: &AppHandle) -> Result<(), String> {
     let manager = app.state::<WindowManager>();
@@ -381,6 +627,10 @@ pub fn restore_windows(app: &AppHandle) {
                     y: None,
                     width: None,
                     height: None,
+                    maximized: false,
+                    font_size: None,
+                    theme: None,
+                    zoom_level: None,
                 },
             );
             return;
@@ -410,6 +660,7 @@ pub fn restore_windows(app: &AppHandle) {
     // Get screen bounds once (will be None until first window is available)
     // We'll query again after main window is set up
     let mut screen_bounds: Option<ScreenBounds> = None;
+    let mut monitor_bounds: Vec<ScreenBounds> = Vec::new();
 
     for info in &states.windows {
         if info.label == "main" {
@@ -418,34 +669,51 @@ pub fn restore_windows(app: &AppHandle) {
             if let Some(win) = app.get_webview_window("main") {
                 // Now we can get screen bounds from the main window
                 if screen_bounds.is_none() {
-                    screen_bounds = win.primary_monitor().ok().flatten().map(|monitor| {
-                        let size = monitor.size();
-                        let position = monitor.position();
-                        let scale = monitor.scale_factor();
-                        ScreenBounds {
-                            x: position.x as f64,
-                            y: position.y as f64,
-                            width: size.width as f64 / scale,
-                            height: size.height as f64 / scale,
-                        }
-                    });
+                    screen_bounds = win
+                        .primary_monitor()
+                        .ok()
+                        .flatten()
+                        .map(|monitor| ScreenBounds::from_monitor(&monitor));
+                }
+                if monitor_bounds.is_empty() {
+                    monitor_bounds = win
+                        .available_monitors()
+                        .map(|monitors| monitors.iter().map(ScreenBounds::from_monitor).collect())
+                        .unwrap_or_default();
                 }
 
-                // Sanitize dimensions with screen awareness
-                let (w, h) = sanitize_dimensions_for_screen(info.width, info.height, screen_bounds);
-
-                // Sanitize position - may return None if off-screen
-                let position = sanitize_position_for_screen(info.x, info.y, w, h, screen_bounds);
-
-                // Apply size first, then position
-                let _ = win.set_size(tauri::Size::Logical(tauri::LogicalSize::new(w, h)));
-
-                if let Some((x, y)) = position {
-                    let _ = win
-                        .set_position(tauri::Position::Logical(tauri::LogicalPosition::new(x, y)));
+                if is_valid_maximized(info) {
+                    let _ = win.maximize();
                 } else {
-                    // Center the window if position was invalid/off-screen
-                    let _ = win.center();
+                    // Sanitize dimensions with screen awareness
+                    let (w, h) =
+                        sanitize_dimensions_for_screen(info.width, info.height, screen_bounds);
+
+                    // Sanitize position - may return None if off-screen on every monitor
+                    let position = sanitize_position_for_screen(
+                        info.x,
+                        info.y,
+                        w,
+                        h,
+                        screen_bounds,
+                        &monitor_bounds,
+                    );
+
+                    // Apply size first, then position
+                    let _ = win.set_size(tauri::Size::Logical(tauri::LogicalSize::new(w, h)));
+
+                    if let Some((x, y)) = position {
+                        let _ = win.set_position(tauri::Position::Logical(
+                            tauri::LogicalPosition::new(x, y),
+                        ));
+                    } else {
+                        // Center the window if position was invalid/off-screen
+                        let _ = win.center();
+                    }
+                }
+
+                if let Some(zoom) = info.zoom_level {
+                    let _ = win.set_zoom(clamp_zoom(zoom));
                 }
             }
 
@@ -456,12 +724,16 @@ pub fn restore_windows(app: &AppHandle) {
             if screen_bounds.is_none() {
                 screen_bounds = ScreenBounds::from_app(app);
             }
+            if monitor_bounds.is_empty() {
+                monitor_bounds = get_all_monitor_bounds(app);
+            }
 
             // Sanitize dimensions with screen awareness
             let (w, h) = sanitize_dimensions_for_screen(info.width, info.height, screen_bounds);
 
-            // Sanitize position
-            let position = sanitize_position_for_screen(info.x, info.y, w, h, screen_bounds);
+            // Sanitize position against all connected monitors
+            let position =
+                sanitize_position_for_screen(info.x, info.y, w, h, screen_bounds, &monitor_bounds);
 
             // Create additional windows
             let mut builder = WebviewWindowBuilder::new(app, &info.label, WebviewUrl::default())
@@ -470,12 +742,17 @@ pub fn restore_windows(app: &AppHandle) {
                 .inner_size(w, h)
                 .resizable(true);
 
-            if let Some((x, y)) = position {
+            if is_valid_maximized(info) {
+                builder = builder.maximized(true);
+            } else if let Some((x, y)) = position {
                 builder = builder.position(x, y);
             }
             // If position is None, window will be auto-positioned by the system
 
-            if builder.build().is_ok() {
+            if let Ok(win) = builder.build() {
+                if let Some(zoom) = info.zoom_level {
+                    let _ = win.set_zoom(clamp_zoom(zoom));
+                }
                 let mut windows = manager.windows.lock().unwrap_or_else(|e| e.into_inner());
                 windows.insert(info.label.clone(), info.clone());
             }
@@ -607,6 +884,56 @@ mod tests {
         assert!(!is_valid_height(f64::INFINITY));
     }
 
+    #[test]
+    fn sanitize_position_uses_any_visible_monitor() {
+        let primary = ScreenBounds {
+            x: 0.0,
+            y: 0.0,
+            width: 1920.0,
+            height: 1080.0,
+        };
+        let secondary = ScreenBounds {
+            x: 1920.0,
+            y: 0.0,
+            width: 1920.0,
+            height: 1080.0,
+        };
+        let monitors = [primary, secondary];
+
+        assert_eq!(
+            sanitize_position_for_screen(
+                Some(2100.0),
+                Some(100.0),
+                1400.0,
+                900.0,
+                Some(primary),
+                &monitors
+            ),
+            Some((2100.0, 100.0))
+        );
+        // Secondary monitor disconnected: fall back to centering
+        assert_eq!(
+            sanitize_position_for_screen(
+                Some(2100.0),
+                Some(100.0),
+                1400.0,
+                900.0,
+                Some(primary),
+                &[primary]
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn encode_query_value_escapes_reserved_characters() {
+        assert_eq!(encode_query_value("revi-1"), "revi-1");
+        assert_eq!(
+            encode_query_value("/Users/me/my repo&x=1"),
+            "%2FUsers%2Fme%2Fmy%20repo%26x%3D1"
+        );
+    }
+
     #[test]
     fn sanitize_dimension_single_value() {
         assert_eq!(
@@ -616,4 +943,12 @@ mod tests {
         assert_eq!(sanitize_dimension(100.0, MIN_WIDTH, MAX_WIDTH), None);
         assert_eq!(sanitize_dimension(10000.0, MIN_WIDTH, MAX_WIDTH), None);
     }
+
+    #[test]
+    fn clamp_zoom_limits_range() {
+        assert_eq!(clamp_zoom(1.25), 1.25);
+        assert_eq!(clamp_zoom(0.1), MIN_ZOOM);
+        assert_eq!(clamp_zoom(10.0), MAX_ZOOM);
+        assert_eq!(clamp_zoom(f64::NAN), DEFAULT_ZOOM);
+    }
 }
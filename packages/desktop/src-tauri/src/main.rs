@@ -18,21 +18,107 @@ fn main() {
         .invoke_handler(tauri::generate_handler![
             session::get_session_arg,
             session::load_session,
+            session::load_session_auto_root,
             session::save_review_state,
+            session::save_session_lock,
+            session::release_session_lock,
             session::load_review_state,
             session::recover_state,
+            session::get_review_analytics,
+            session::get_all_sessions_overview,
+            session::get_session_reviewed_pct,
+            session::get_session_file_subset,
+            session::get_session_files_by_extension,
+            session::export_review_progress_csv,
             session::create_session_from_repo,
+            session::refresh_session,
+            session::create_session_from_pr_url,
             session::save_last_session,
             session::load_last_session,
             session::clear_last_session,
             session::list_branches,
             session::list_recent_commits,
+            session::list_commits_between,
+            session::get_cherry_pickable_commits,
+            session::get_commits_for_session_files,
+            session::get_commit_body,
+            session::get_files_changed_by_author,
+            session::save_session_notes,
+            session::get_session_notes,
+            session::set_review_status,
+            session::get_review_status,
+            session::get_ignored_files,
+            session::get_gitignore_patterns,
+            session::get_diff_file_tree,
+            session::get_auto_expand_policy,
+            session::get_change_density,
+            session::get_directory_change_summary,
+            session::get_file_review_checklist,
+            session::duplicate_session,
+            session::get_merge_base_for_session,
+            session::get_merge_in_progress,
+            session::get_session_age_warning,
+            session::set_comparison_range,
+            session::get_diff_for_commit,
+            session::export_session_portable,
+            session::import_portable_session,
+            session::get_uncommitted_staged_files,
+            session::get_uncommitted_unstaged_files,
+            session::get_stash_diff,
+            session::get_branch_graph,
+            session::list_tags,
+            session::get_commit_signing_status,
+            session::is_ref_reachable,
+            session::get_common_ancestor,
+            session::get_sparse_checkout_patterns,
+            session::get_git_config,
             git::get_file_diff,
+            git::get_renamed_file_diff,
+            git::get_stash_file_diff,
+            git::get_file_diff_with_comments,
+            git::get_uncommitted_snapshot,
+            git::get_diff_for_deleted_file,
+            git::get_file_diff_between_commits,
+            git::get_file_diff_stats_only,
             git::compute_content_hash,
+            git::compute_session_content_hash,
+            git::get_blame_author_stats,
+            git::get_highlighted_blame,
+            git::get_diff_with_blame,
+            git::check_git_version,
+            git::get_git_version_warning,
+            git::get_gitattributes_diff_settings,
+            git::get_word_regex_for_file,
+            git::get_file_stats,
+            git::get_binary_size_diff,
+            git::get_line_ending_info,
+            git::get_diff_word_stats,
+            git::get_diff_token_count,
+            git::normalize_diff_spans,
+            git::get_submodule_diff,
+            git::get_conflict_sections,
+            git::get_shortstat_summary,
+            git::get_patch_id,
+            git::get_commits_with_stats,
+            git::get_file_mode,
+            git::search_in_session,
+            git::get_file_context_range,
+            git::get_highlighted_file,
+            git::find_line_in_diff,
             git::invalidate_diff_cache,
+            git::invalidate_file_diff_cache,
             git::clear_diff_cache,
+            git::get_diff_cache_entries,
+            git::evict_file_from_cache,
             highlight::highlight_code,
+            highlight::highlight_multiple_files,
+            highlight::get_highlight_scope_legend,
+            highlight::get_scopes_used_in_file,
+            highlight::normalize_spans_to_char_offsets,
             highlight::detect_language,
+            highlight::detect_language_advanced,
+            highlight::load_external_grammar,
+            highlight::list_external_grammars,
             window::create_window,
             window::register_window_session,
             window::save_window_states,
@@ -40,11 +126,28 @@ fn main() {
             window::get_window_session,
             window::find_window_by_repo,
             window::focus_window_and_close,
+            window::set_window_font_size,
+            window::set_window_theme,
+            window::set_window_zoom_level,
+            window::get_window_zoom_level,
+            window::broadcast_to_windows,
+            window::close_all_windows,
+            window::open_session_picker,
             file_ops::open_in_editor,
             file_ops::copy_to_clipboard,
             file_ops::get_binary_preview,
+            file_ops::get_image_diff_base64,
+            file_ops::get_untracked_file_preview,
+            file_ops::reveal_in_file_manager,
+            file_ops::copy_file_path_to_clipboard,
+            file_ops::copy_relative_path,
+            file_ops::copy_diff_as_markdown,
             watcher::start_watching,
             watcher::stop_watching,
+            watcher::get_watcher_patterns,
+            watcher::ping_watcher,
+            watcher::get_watcher_status,
+            watcher::get_watcher_default_ignores,
         ])
         .setup(|app| {
             // Build the File menu
@@ -96,13 +199,20 @@ fn main() {
                 WindowEvent::Resized(size) => {
                     let width = size.width as f64;
                     let height = size.height as f64;
+                    let maximized = window.is_maximized().unwrap_or(false);
 
-                    // Only store valid dimensions to prevent corrupted state
-                    if window::is_valid_width(width) && window::is_valid_height(height) {
-                        let manager = app.state::<window::WindowManager>();
-                        let mut windows =
-                            manager.windows.lock().unwrap_or_else(|e| e.into_inner());
-                        if let Some(info) = windows.get_mut(&label) {
+                    let manager = app.state::<window::WindowManager>();
+                    let mut windows =
+                        manager.windows.lock().unwrap_or_else(|e| e.into_inner());
+                    if let Some(info) = windows.get_mut(&label) {
+                        info.maximized = maximized;
+
+                        // Keep the pre-maximize size so un-maximizing restores it.
+                        // Only store valid dimensions to prevent corrupted state
+                        if !maximized
+                            && window::is_valid_width(width)
+                            && window::is_valid_height(height)
+                        {
                             info.width = Some(width);
                             info.height = Some(height);
                         }
@@ -116,6 +226,7 @@ fn main() {
                     let mut windows =
                         manager.windows.lock().unwrap_or_else(|e| e.into_inner());
                     windows.remove(&label);
+                    session::release_window_session_locks(&label);
                 }
                 _ => {}
             }
@@ -3,10 +3,11 @@ import { Sidebar } from './components/layout/Sidebar';
 import { DiffPane } from './components/layout/DiffPane';
 import { ErrorBoundary } from './components/layout/ErrorBoundary';
 import { KeyboardHelp } from './components/overlays/KeyboardHelp';
+import { GitVersionBanner } from './components/overlays/GitVersionBanner';
 import { useSessionStore } from './stores/session';
 import { useReviewStateStore } from './stores/reviewState';
 import { KEYBINDINGS, matchesKeybinding } from './keyboard/keymap';
-import { useKeyboardManager } from './hooks/useKeyboardManager';
+import { syncZoomLevel, useKeyboardManager } from './hooks/useKeyboardManager';
 import { useEffect, useState, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen, UnlistenFn } from '@tauri-apps/api/event';
@@ -18,6 +19,14 @@ interface ChangeEvent {
   repoRoot: string; // Which repo this change is for
   paths?: string[];
   newHeadSha?: string;
+  truncated?: boolean; // Too many paths to list; refresh everything
+  totalPathCount?: number;
+}
+
+interface WindowSettings {
+  fontSize: number | null;
+  theme: string | null;
+  zoomLevel: number | null;
 }
 
 export function App() {
@@ -83,6 +92,43 @@ export function App() {
     initSession();
   }, [loadSession, loadSessionFromRepo, loadLastSession]);
 
+  // A session chosen in this window's session picker
+  useEffect(() => {
+    const unlisten = getCurrentWebviewWindow().listen<{ repoRoot: string; sessionId: string }>(
+      'session-selected',
+      (event) => {
+        const { repoRoot, sessionId } = event.payload;
+        loadSession(`${repoRoot}/.revi/sessions/${sessionId}.json`);
+      }
+    );
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [loadSession]);
+
+  // Per-window display overrides pushed by the backend
+  useEffect(() => {
+    const currentWindow = getCurrentWebviewWindow();
+    const unlisten = currentWindow.listen<WindowSettings>('window-settings-changed', (event) => {
+      const { fontSize, theme, zoomLevel } = event.payload;
+      const root = document.documentElement;
+      root.style.fontSize = fontSize != null ? `${fontSize}px` : '';
+      if (theme) {
+        root.dataset.theme = theme;
+      } else {
+        delete root.dataset.theme;
+      }
+      if (zoomLevel != null) {
+        syncZoomLevel(zoomLevel);
+      }
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   // Central keyboard handler — must be called unconditionally (rules of hooks)
   useKeyboardManager();
 
@@ -172,7 +218,8 @@ export function App() {
     };
 
     // Start watching the repository
-    invoke('start_watching', { repoRoot }).catch((err) => {
+    const watchOptions = { repoRoot, includePatterns: [], excludePatterns: [], usePolling: false };
+    invoke('start_watching', watchOptions).catch((err) => {
       console.warn('Failed to start file watcher:', err);
     });
 
@@ -186,12 +233,17 @@ export function App() {
       }
 
       const modeType = session.comparisonMode?.type;
-      const isUncommittedMode = modeType === 'uncommitted';
+      // Modes that read the working tree or index (conflict lists unmerged files)
+      const isWorkingTreeMode =
+        modeType === 'uncommitted' ||
+        modeType === 'staged' ||
+        modeType === 'unstaged' ||
+        modeType === 'conflict';
       const isRefChange = event.payload.type === 'ref_changed';
 
       // In branch/custom mode, ignore working-tree file changes.
       // Only ref changes can affect the compared commits.
-      if (!isUncommittedMode && !isRefChange) {
+      if (!isWorkingTreeMode && !isRefChange) {
         return;
       }
 
@@ -311,6 +363,7 @@ export function App() {
   if (!session) {
     return (
       <div className="app app--empty">
+        <GitVersionBanner />
         <div className="empty-state">
           <h1>Revi</h1>
           <p>Local code review made simple</p>
@@ -328,6 +381,7 @@ export function App() {
   return (
     <div className="app">
       <TopBar />
+      <GitVersionBanner />
       <div className="app__body">
         <Sidebar />
         <ErrorBoundary>
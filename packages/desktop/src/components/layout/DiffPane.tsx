@@ -416,6 +416,7 @@ interface DiffHeaderProps {
     deletions: number;
     status: string;
     renamedFrom?: string;
+    renameScore?: number;
   };
   repoRoot: string;
   isCollapsed: boolean;
@@ -465,7 +466,8 @@ function DiffHeader({
         </button>
         {file.renamedFrom && (
           <span className="diff-pane__renamed">
-            {file.renamedFrom} →{' '}
+            {file.renamedFrom}
+            {file.renameScore !== undefined && ` (${file.renameScore}% similar)`} →{' '}
           </span>
         )}
         <span className="diff-pane__path">{file.path}</span>
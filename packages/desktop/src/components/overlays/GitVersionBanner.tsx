@@ -0,0 +1,33 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { WarningIcon, XIcon } from '@phosphor-icons/react';
+
+/** Warns (but doesn't block) when the installed git is older than Revi supports */
+export function GitVersionBanner() {
+  const [warning, setWarning] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<string | null>('get_git_version_warning')
+      .then(setWarning)
+      .catch(console.error);
+  }, []);
+
+  if (!warning) return null;
+
+  return (
+    <div className="refresh-banner refresh-banner--warning">
+      <span className="refresh-banner__text">
+        <WarningIcon size={14} /> {warning}
+      </span>
+      <div className="refresh-banner__actions">
+        <button
+          className="refresh-banner__btn refresh-banner__btn--secondary"
+          onClick={() => setWarning(null)}
+          title="Dismiss"
+        >
+          <XIcon size={14} />
+        </button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,74 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { emitTo } from '@tauri-apps/api/event';
+import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
+
+interface SessionOverview {
+  sessionId: string;
+  name: string | null;
+  createdAt: string;
+  status: string;
+  totalFiles: number;
+  viewedFiles: number;
+  totalChanges: number;
+  lastActivity: string | null;
+}
+
+interface Props {
+  repoRoot: string;
+  parentLabel: string;
+}
+
+/**
+ * Contents of the window opened by `open_session_picker`. Sends
+ * "session-selected" to the parent window and closes itself.
+ */
+export function SessionPicker({ repoRoot, parentLabel }: Props) {
+  const [sessions, setSessions] = useState<SessionOverview[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<SessionOverview[]>('get_all_sessions_overview', { repoRoot })
+      .then(setSessions)
+      .catch((err) => setError(String(err)));
+  }, [repoRoot]);
+
+  const close = () => getCurrentWebviewWindow().close();
+
+  const handleSelect = async (sessionId: string) => {
+    try {
+      await emitTo(parentLabel, 'session-selected', { repoRoot, sessionId });
+      await close();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <div className="session-picker">
+      <div className="modal__header">
+        <h2>Select Session</h2>
+        <button className="modal__close" onClick={close}>
+          ×
+        </button>
+      </div>
+      <div className="session-picker__list">
+        {error && <p className="error">Error: {error}</p>}
+        {!error && sessions.length === 0 && <p className="dim">No sessions for this repository</p>}
+        {sessions.map((s) => (
+          <button
+            key={s.sessionId}
+            className="session-picker__item"
+            onClick={() => handleSelect(s.sessionId)}
+          >
+            <span className="session-picker__name">{s.name ?? s.sessionId}</span>
+            <span className="dim">
+              {s.viewedFiles} / {s.totalFiles} files viewed ·{' '}
+              {new Date(s.lastActivity ?? s.createdAt).toLocaleString()}
+            </span>
+          </button>
+        ))}
+      </div>
+    </div>
+  );
+}
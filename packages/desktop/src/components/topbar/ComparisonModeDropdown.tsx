@@ -19,6 +19,7 @@ export function ComparisonModeDropdown({
   const [isOpen, setIsOpen] = useState(false);
   const [showCustomModal, setShowCustomModal] = useState(false);
   const [branches, setBranches] = useState<string[]>([]);
+  const [mergeInProgress, setMergeInProgress] = useState(false);
   const dropdownRef = useRef<HTMLDivElement>(null);
 
   // Get the display label for the current mode
@@ -27,10 +28,16 @@ export function ComparisonModeDropdown({
     switch (mode.type) {
       case 'uncommitted':
         return 'Uncommitted Changes';
+      case 'staged':
+        return 'Staged Changes';
+      case 'unstaged':
+        return 'Unstaged Changes';
       case 'branch':
         return `Branch (vs ${mode.baseBranch})`;
       case 'custom':
         return `${mode.baseRef}..${mode.headRef}`;
+      case 'conflict':
+        return 'Merge in Progress';
       default:
         return 'Auto';
     }
@@ -57,6 +64,15 @@ export function ComparisonModeDropdown({
     }
   }, [isOpen, repoRoot, branches.length]);
 
+  // Conflict mode is only offered while a merge is in progress
+  useEffect(() => {
+    if (isOpen) {
+      invoke<boolean>('get_merge_in_progress', { repoRoot })
+        .then(setMergeInProgress)
+        .catch(console.error);
+    }
+  }, [isOpen, repoRoot]);
+
   const handleModeSelect = (mode: ComparisonMode) => {
     onModeChange(mode);
     setIsOpen(false);
@@ -102,6 +118,21 @@ export function ComparisonModeDropdown({
                 </span>
               )}
             </button>
+            {mergeInProgress && (
+              <button
+                className={clsx('comparison-mode-dropdown__item', {
+                  'is-active': currentMode?.type === 'conflict',
+                })}
+                onClick={() => handleModeSelect({ type: 'conflict' })}
+              >
+                <span className="comparison-mode-dropdown__item-label">
+                  Merge in Progress
+                </span>
+                <span className="comparison-mode-dropdown__item-desc">
+                  merge-base vs MERGE_HEAD
+                </span>
+              </button>
+            )}
           </div>
 
           <div className="comparison-mode-dropdown__section">
@@ -14,6 +14,11 @@ let currentZoom = 1.0;
 const ZOOM_STEP = 0.1;
 const ZOOM_MIN = 0.5;
 const ZOOM_MAX = 2.0;
+/** Keep the shortcut zoom in step with zoom applied from the backend */
+export function syncZoomLevel(zoom: number) {
+  currentZoom = zoom;
+}
+
 const MANAGER_KEYBINDINGS = KEYBINDINGS.filter((binding) => binding.scope === 'manager');
 
 /**
@@ -1,10 +1,21 @@
 import React from 'react';
 import ReactDOM from 'react-dom/client';
 import { App } from './App';
+import { SessionPicker } from './components/overlays/SessionPicker';
 import './styles/index.css';
 
-ReactDOM.createRoot(document.getElementById('root')!).render(
-  <React.StrictMode>
+// Secondary windows pick their view from the URL (see open_session_picker)
+const params = new URLSearchParams(window.location.search);
+const view =
+  params.get('view') === 'session-picker' ? (
+    <SessionPicker
+      repoRoot={params.get('repoRoot') ?? ''}
+      parentLabel={params.get('parent') ?? ''}
+    />
+  ) : (
     <App />
-  </React.StrictMode>
+  );
+
+ReactDOM.createRoot(document.getElementById('root')!).render(
+  <React.StrictMode>{view}</React.StrictMode>
 );
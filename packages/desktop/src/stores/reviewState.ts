@@ -1,5 +1,6 @@
 import { create } from 'zustand';
 import { invoke } from '@tauri-apps/api/core';
+import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
 import type { PersistedState, FileState, DiffStats, CollapseState, DiffMode } from '@revi/shared';
 
 /**
@@ -322,6 +323,7 @@ export const useReviewStateStore = create<ReviewStateStore>((set, get) => ({
       await invoke('save_review_state', {
         repoRoot,
         state,
+        windowLabel: getCurrentWebviewWindow().label,
       });
     } catch (error) {
       console.error('Failed to save review state:', error);
@@ -9,7 +9,14 @@ function isComparisonMode(value: unknown): value is ComparisonMode {
   if (!value || typeof value !== 'object') return false;
   const mode = value as { type?: unknown };
 
-  if (mode.type === 'uncommitted') return true;
+  if (
+    mode.type === 'uncommitted' ||
+    mode.type === 'staged' ||
+    mode.type === 'unstaged' ||
+    mode.type === 'conflict'
+  ) {
+    return true;
+  }
   if (mode.type === 'branch') {
     return typeof (value as { baseBranch?: unknown }).baseBranch === 'string';
   }
@@ -239,14 +246,10 @@ export const useSessionStore = create<SessionState>((set, get) => ({
       // Invalidate diff cache before refreshing
       await invoke('invalidate_diff_cache', { repoRoot: session.repoRoot }).catch(() => {});
 
-      // Get the current comparison mode from the session
-      const mode = session.comparisonMode ?? null;
-
-      // Recreate the session with the same mode
-      const manifest = await invoke<ReviewManifest>('create_session_from_repo', {
-        repoPath: session.repoRoot,
-        baseRef: null,
-        mode,
+      // Re-read files under the same comparison mode, keeping the session id
+      const manifest = await invoke<ReviewManifest>('refresh_session', {
+        repoRoot: session.repoRoot,
+        sessionId: session.sessionId,
       });
 
       if (manifest.comparisonMode) {
@@ -8,6 +8,17 @@ export interface ReviConfig {
   exclude?: string[];
   dangerZone?: string[];
   keybindings?: Record<string, string>;
+  watcher?: {
+    polling?: boolean; // Poll instead of native events (NFS, CIFS, Docker mounts)
+    pollIntervalMs?: number;
+    debounceMs?: number; // Applied live when config.json is edited
+  };
+  cache?: {
+    // App-wide: one cache serves every open repo, so the last applied size wins.
+    // Applied live when config.json is edited.
+    diffCacheSize?: number;
+  };
+  renameThreshold?: number; // Similarity % for rename detection (git default: 50)
 }
 
 export const DEFAULT_CONFIG: ReviConfig = {
@@ -1,3 +1,6 @@
+import type { FileEntry } from './manifest';
+import type { CommentThread } from './state';
+
 /**
  * Structured diff data returned from Rust backend
  */
@@ -6,6 +9,30 @@ export interface FileDiff {
   hunks: Hunk[];
   contentHash: string;
   stats: DiffStats;
+  skippedByAttributes: boolean; // File is marked `-diff` in .gitattributes
+  summary: string; // One-liner, e.g. "+42 −17 in 3 hunks"
+  lineEnding: 'lf' | 'crlf' | 'mixed' | 'none' | ''; // Empty when the diff was skipped
+  renamedFrom?: string | null; // Base-side path, set by get_renamed_file_diff
+}
+
+/**
+ * A file's diff plus its comment threads, fetched in one call
+ */
+export interface FileDiffWithComments {
+  diff: FileDiff;
+  threads: CommentThread[];
+}
+
+export interface DeletedFileDiff {
+  diff: FileDiff;
+  deletedAtCommit: string;
+  finalAuthor: string;
+  finalDate: string; // ISO 8601
+}
+
+export interface UncommittedSnapshot {
+  files: FileEntry[];
+  diffs: Record<string, FileDiff>;
 }
 
 export interface DiffStats {
@@ -20,6 +47,8 @@ export interface Hunk {
   newStart: number;
   newLines: number;
   lines: DiffLine[];
+  contextName?: string | null; // Enclosing function/class from the `@@` line
+  contextLanguageHint?: string | null; // File language, set when contextName is
 }
 
 export interface DiffLine {
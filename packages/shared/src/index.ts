@@ -7,6 +7,11 @@ export type {
   FileStatus,
   ComparisonMode,
   CommitInfo,
+  CommitBody,
+  PrInfo,
+  ReviewStatus,
+  PortableSession,
+  TreeNode,
 } from './manifest';
 
 // State types
@@ -18,11 +23,16 @@ export type {
   DiffMode,
   RecoveredState,
   FileRecovery,
+  CommentThread,
+  Comment,
 } from './state';
 
 // Diff types
 export type {
   FileDiff,
+  FileDiffWithComments,
+  UncommittedSnapshot,
+  DeletedFileDiff,
   DiffStats,
   Hunk,
   DiffLine,
@@ -19,4 +19,6 @@ export interface RepoChangedEvent {
   type: 'file_changed' | 'ref_changed' | 'commit_added';
   paths?: string[];
   newHeadSha?: string;
+  truncated?: boolean; // Too many paths to list; refresh everything
+  totalPathCount?: number;
 }
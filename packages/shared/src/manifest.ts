@@ -12,6 +12,30 @@ export interface ReviewManifest {
   files: FileEntry[];
   createdAt: string;
   comparisonMode?: ComparisonMode; // Added in Phase 6b
+  prInfo?: PrInfo; // Set when the session was created from a GitHub PR URL
+  skippedCount?: number; // Files hidden because they are outside the sparse-checkout cone
+  notes?: string; // Free-form reviewer notes
+  status?: ReviewStatus; // Defaults to 'pending'
+  name?: string;
+  tags?: string[];
+  isSingleCommit?: boolean; // Session covers exactly one commit (`<sha>^..<sha>`)
+}
+
+/**
+ * Shareable session: local paths replaced by the origin URL and branch name
+ */
+export interface PortableSession extends Omit<ReviewManifest, 'repoRoot' | 'worktree'> {
+  originUrl: string | null;
+  branch: string | null;
+}
+
+export type ReviewStatus = 'pending' | 'inProgress' | 'approved' | 'changesRequested';
+
+export interface PrInfo {
+  title: string;
+  number: number;
+  htmlUrl: string;
+  author: string;
 }
 
 export interface RefInfo {
@@ -31,17 +55,34 @@ export interface FileEntry {
   deletions: number;
   renamedFrom?: string;
   binary: boolean;
+  locallyAbsent?: boolean; // Not present in the working tree (e.g. sparse-checkout)
+  conflictSide?: 'both' | 'theirs'; // Conflict mode: conflicted vs theirs-only change
+  renameScore?: number; // Rename similarity percentage (0-100), from git's R<score> status
 }
 
 export type FileStatus = 'added' | 'modified' | 'deleted' | 'renamed';
 
+/**
+ * Changed files arranged as a directory tree (directories first, then alphabetical)
+ */
+export interface TreeNode {
+  name: string;
+  path: string;
+  isDir: boolean;
+  children: TreeNode[];
+  entry: FileEntry | null; // Set on file (leaf) nodes
+}
+
 /**
  * Comparison Mode - determines what is being compared in the review
  */
 export type ComparisonMode =
   | { type: 'uncommitted' } // HEAD vs Working Tree (staged + unstaged + untracked)
+  | { type: 'staged' } // HEAD vs Index
+  | { type: 'unstaged' } // Index vs Working Tree
   | { type: 'branch'; baseBranch: string } // merge-base(baseBranch)..HEAD
-  | { type: 'custom'; baseRef: string; headRef: string }; // Custom ref comparison
+  | { type: 'custom'; baseRef: string; headRef: string } // Custom ref comparison
+  | { type: 'conflict' }; // merge-base(HEAD, MERGE_HEAD)..MERGE_HEAD during a merge
 
 /**
  * Commit info for listing recent commits
@@ -52,4 +93,15 @@ export interface CommitInfo {
   message: string;
   author: string;
   date: string;
+  parentCount: number; // > 1 for merge commits
+  bodyPreview?: string; // First 200 characters of the message body
+}
+
+/**
+ * Full commit message from get_commit_body
+ */
+export interface CommitBody {
+  subject: string;
+  body: string;
+  trailers: Record<string, string[]>; // e.g. "Co-authored-by" -> [...]
 }
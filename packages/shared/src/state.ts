@@ -11,6 +11,24 @@ export interface PersistedState {
   headSha: string;
   files: Record<string, FileState>;
   ui: UIState;
+  threads?: CommentThread[];
+}
+
+/**
+ * A discussion anchored to a line of a file's diff
+ */
+export interface CommentThread {
+  id: string;
+  path: string;
+  line: number; // Line number on `side` of the diff
+  side: 'old' | 'new';
+  comments: Comment[];
+  resolved?: boolean;
+}
+
+export interface Comment {
+  body: string;
+  createdAt: string;
 }
 
 export interface FileState {
@@ -20,6 +38,7 @@ export interface FileState {
   diffStats: DiffStats;
   collapseState: CollapseState;
   scrollPosition: number;
+  lastViewedAt?: string;
 }
 
 export interface CollapseState {